@@ -0,0 +1,89 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use hilbert_index::{indices, points, FromHilbertIndex, ToHilbertIndex};
+
+// 他の crate との比較. 2 次元は fast_hilbert, 3 次元は hilbert と比べる.
+// 曲線の向きは crate ごとに異なるので, 速度だけを比較する.
+
+const LEVELS: [usize; 4] = [4, 8, 12, 16];
+
+// 全ての添字を変換するのは 2^16 点以下の場合に限る
+const FULL_RANGE_BITS: usize = 16;
+
+fn sample<const D: usize>(level: usize) -> (Vec<usize>, Vec<[usize; D]>) {
+    let n = 1usize << (D*level);
+    let keys: Vec<usize> = indices::<D>(level).step_by((n/4096).max(1)).collect();
+    let points: Vec<[usize; D]> = keys.iter().map(|h| h.from_hilbert_index(level)).collect();
+    (keys, points)
+}
+
+fn dim_two(c: &mut Criterion) {
+    let mut group = c.benchmark_group("D=2");
+    for &level in LEVELS.iter() {
+        let (keys, points) = sample::<2>(level);
+        let xy: Vec<(u32, u32)> = points.iter().map(|p| (p[0] as u32, p[1] as u32)).collect();
+        let order = level as u8;
+        group.throughput(Throughput::Elements(keys.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("encode/hilbert_index", level), &level, |b, &level| b.iter(|| {
+            points.iter().fold(0, |s, p| s^black_box(p).to_hilbert_index(level))
+        }));
+        group.bench_with_input(BenchmarkId::new("encode/fast_hilbert", level), &order, |b, &order| b.iter(|| {
+            xy.iter().fold(0, |s, &(x, y)| s^fast_hilbert::xy2h(black_box(x), black_box(y), order))
+        }));
+        group.bench_with_input(BenchmarkId::new("decode/hilbert_index", level), &level, |b, &level| b.iter(|| {
+            keys.iter().fold(0, |s, h| { let p: [usize; 2] = black_box(h).from_hilbert_index(level); s^p[0] })
+        }));
+        group.bench_with_input(BenchmarkId::new("decode/fast_hilbert", level), &order, |b, &order| b.iter(|| {
+            keys.iter().fold(0, |s, &h| { let (x, _): (u32, u32) = fast_hilbert::h2xy(black_box(h as u64), order); s^x })
+        }));
+    }
+    group.finish();
+}
+
+fn dim_three(c: &mut Criterion) {
+    let mut group = c.benchmark_group("D=3");
+    for &level in LEVELS.iter() {
+        let (keys, points) = sample::<3>(level);
+        let axes: Vec<[u32; 3]> = points.iter().map(|p| [p[0] as u32, p[1] as u32, p[2] as u32]).collect();
+        let big: Vec<_> = axes.iter().map(|p| hilbert::fast_hilbert::hilbert_index(p, level, None)).collect();
+        group.throughput(Throughput::Elements(keys.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("encode/hilbert_index", level), &level, |b, &level| b.iter(|| {
+            points.iter().fold(0, |s, p| s^black_box(p).to_hilbert_index(level))
+        }));
+        group.bench_with_input(BenchmarkId::new("encode/hilbert", level), &level, |b, &level| b.iter(|| {
+            axes.iter().fold(0, |s, p| s^hilbert::fast_hilbert::hilbert_index(black_box(p), level, None).bits())
+        }));
+        group.bench_with_input(BenchmarkId::new("decode/hilbert_index", level), &level, |b, &level| b.iter(|| {
+            keys.iter().fold(0, |s, h| { let p: [usize; 3] = black_box(h).from_hilbert_index(level); s^p[0] })
+        }));
+        group.bench_with_input(BenchmarkId::new("decode/hilbert", level), &level, |b, &level| b.iter(|| {
+            big.iter().fold(0, |s, h| s^hilbert::fast_hilbert::hilbert_axes(black_box(h), level, 3)[0])
+        }));
+    }
+    group.finish();
+}
+
+// 曲線上の全ての点を生成する場合の throughput
+fn full_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full range");
+    for &level in LEVELS.iter().filter(|&&level| 2*level <= FULL_RANGE_BITS) {
+        group.throughput(Throughput::Elements(1 << (2*level)));
+        group.bench_with_input(BenchmarkId::new("D=2/points", level), &level, |b, &level| b.iter(|| {
+            points::<2>(level).fold(0, |s, p| s^p[0])
+        }));
+        group.bench_with_input(BenchmarkId::new("D=2/fast_hilbert", level), &(level as u8), |b, &order| b.iter(|| {
+            (0..1u64 << (2*order)).fold(0, |s, h| { let (x, _): (u32, u32) = fast_hilbert::h2xy(h, order); s^x })
+        }));
+    }
+    for &level in LEVELS.iter().filter(|&&level| 3*level <= FULL_RANGE_BITS) {
+        group.throughput(Throughput::Elements(1 << (3*level)));
+        group.bench_with_input(BenchmarkId::new("D=3/points", level), &level, |b, &level| b.iter(|| {
+            points::<3>(level).fold(0, |s, p| s^p[0])
+        }));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, dim_two, dim_three, full_range);
+criterion_main!(benches);
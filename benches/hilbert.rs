@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use hilbert_index::{from_hilbert_index_as, gray, indices, FromHilbertIndex, HilbertTransform, ToHilbertIndex};
+
+// 2 次元と 3 次元の状態遷移表による高速化を, 1 level ずつ処理する汎用版と比べる.
+// HilbertTransform の表引きも trait と比べる.
+fn bench<const D: usize>(c: &mut Criterion, level: usize) {
+    let keys: Vec<usize> = indices::<D>(level).step_by(7).take(4096).collect();
+    let points: Vec<[usize; D]> = keys.iter().map(|h| h.from_hilbert_index(level)).collect();
+    let curve = HilbertTransform::<D>::new(level);
+
+    let mut group = c.benchmark_group(format!("D={} level={}", D, level));
+    group.bench_function("to_hilbert_index", |b| b.iter(|| {
+        points.iter().fold(0, |s, p| s^black_box(p).to_hilbert_index(level))
+    }));
+    group.bench_function("to_hilbert_index (generic)", |b| b.iter(|| {
+        points.iter().fold(0, |s, p| s^black_box(p).to_hilbert_index_as::<usize>(level))
+    }));
+    group.bench_function("from_hilbert_index", |b| b.iter(|| {
+        keys.iter().fold(0, |s, h| { let p: [usize; D] = black_box(h).from_hilbert_index(level); s^p[0] })
+    }));
+    group.bench_function("from_hilbert_index (generic)", |b| b.iter(|| {
+        keys.iter().fold(0, |s, &h| { let p: [usize; D] = from_hilbert_index_as(black_box(h), level); s^p[0] })
+    }));
+    group.bench_function("HilbertTransform::encode", |b| b.iter(|| {
+        points.iter().fold(0, |s, p| s^curve.encode(black_box(p)))
+    }));
+    group.bench_function("HilbertTransform::decode", |b| b.iter(|| {
+        keys.iter().fold(0, |s, &h| s^curve.decode(black_box(h))[0])
+    }));
+    group.finish();
+}
+
+// D bit の回転を, D = 32 で一致する u32 の組み込み関数と比べる
+fn rotate(c: &mut Criterion) {
+    let words: Vec<usize> = (0..4096usize).map(|k| k.wrapping_mul(0x9e37_79b9) & 0xffff_ffff).collect();
+
+    let mut group = c.benchmark_group("rotate D=32");
+    group.bench_function("gray::rotate_right", |b| b.iter(|| {
+        words.iter().enumerate().fold(0, |s, (i, &w)| s^gray::rotate_right::<32>(black_box(w), i))
+    }));
+    group.bench_function("u32::rotate_right", |b| b.iter(|| {
+        words.iter().enumerate().fold(0, |s, (i, &w)| s^(black_box(w) as u32).rotate_right(i as u32) as usize)
+    }));
+    group.finish();
+}
+
+fn hilbert(c: &mut Criterion) {
+    bench::<2>(c, 16);
+    bench::<3>(c, 16);
+    bench::<5>(c, 12);
+}
+
+criterion_group!(benches, hilbert, rotate);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+// 任意の添字と level を from_hilbert_index に与え, panic せずに箱の中の点を返すことを確認する.
+//
+//     cargo install cargo-fuzz
+//     cargo +nightly fuzz run decode
+#![no_main]
+use hilbert_index::{max_level, FromHilbertIndex, HilbertError, ToHilbertIndex};
+use libfuzzer_sys::fuzz_target;
+
+fn check<const D: usize>(h: usize, level: u8) {
+    // level と添字を有効な範囲に制限する
+    let level = level as usize % (max_level::<D>() + 1);
+    let mask = !( {usize::MAX}<<(D*level) );
+
+    let p: [usize; D] = (h & mask).from_hilbert_index(level);
+    for &x in p.iter() {
+        assert!(x < 1 << level, "{:?} is out of the box of level {}", p, level);
+    }
+    assert_eq!(h & mask, p.to_hilbert_index(level));
+
+    // checked 版は範囲外の添字を拒否する
+    match FromHilbertIndex::<D>::try_from_hilbert_index(&h, level) {
+        Ok(q) => {
+            assert_eq!(h, h & mask);
+            assert_eq!(p, q);
+        }
+        Err(HilbertError::IndexOutOfRange { index, bound }) => {
+            assert_ne!(h, h & mask);
+            assert_eq!((index, bound), (h, 1 << (D*level)));
+        }
+        Err(e) => panic!("unexpected error {:?}", e),
+    }
+}
+
+fuzz_target!(|input: (usize, u8)| {
+    let (h, level) = input;
+    check::<2>(h, level);
+    check::<3>(h, level);
+    check::<4>(h, level);
+    check::<5>(h, level);
+    check::<8>(h, level);
+});
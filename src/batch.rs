@@ -0,0 +1,100 @@
+use crate::{max_level, FromHilbertIndex, ToHilbertIndex};
+
+/// Convert Hilbert indices `src` to grid points, written into `dst`.
+/// 
+/// This is a convenience wrapper equivalent to `dst[i] = src[i].from_hilbert_index(level)` for all `i`,
+/// which does not allocate. Each element is converted by the same code as `from_hilbert_index`,
+/// so it is not faster than the loop; unlike `from_hilbert_index`, `level` is checked also in release builds.
+/// It is the caller's job to prepare `dst` of the same length as `src`.
+/// 
+/// # Panics
+/// 
+/// Panics if `src.len() != dst.len()` or `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::from_hilbert_indices;
+/// 
+/// let mut points = [[0; 2]; 4];
+/// from_hilbert_indices(&[0, 1, 2, 3], 1, &mut points);
+/// assert_eq!( [[0, 0], [0, 1], [1, 1], [1, 0]], points );
+/// ```
+/// 
+pub fn from_hilbert_indices<const D: usize>(src: &[usize], level: usize, dst: &mut [[usize; D]]) {
+    assert_eq!(src.len(), dst.len(),
+        "from_hilbert_indices: src has {} elements, but dst has {}", src.len(), dst.len());
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+
+    for (h, p) in src.iter().zip(dst.iter_mut()) {
+        *p = h.from_hilbert_index(level);
+    }
+}
+
+/// Convert grid points `src` to Hilbert indices, written into `dst`.
+/// 
+/// This is the inverse of `from_hilbert_indices`, and a convenience wrapper equivalent to
+/// `dst[i] = src[i].to_hilbert_index(level)` for all `i`, with `level` checked also in release builds.
+/// It is the caller's job to prepare `dst` of the same length as `src`.
+/// 
+/// # Panics
+/// 
+/// Panics if `src.len() != dst.len()` or `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::to_hilbert_indices;
+/// 
+/// let mut hindices = [0; 4];
+/// to_hilbert_indices(&[[0, 0], [0, 1], [1, 1], [1, 0]], 1, &mut hindices);
+/// assert_eq!( [0, 1, 2, 3], hindices );
+/// ```
+/// 
+pub fn to_hilbert_indices<const D: usize>(src: &[[usize; D]], level: usize, dst: &mut [usize]) {
+    assert_eq!(src.len(), dst.len(),
+        "to_hilbert_indices: src has {} elements, but dst has {}", src.len(), dst.len());
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+
+    for (p, h) in src.iter().zip(dst.iter_mut()) {
+        *h = p.to_hilbert_index(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_indices, to_hilbert_indices, FromHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn same_as_elementwise() {
+        const D: usize = 3;
+        let level = 5;
+
+        // 線形合同法による疑似乱数
+        let mut seed: u64 = 12345;
+        let src: Vec<usize> = (0..1000).map(|_| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as usize % (1 << (D*level))
+        }).collect();
+
+        let mut points = vec![[0; D]; src.len()];
+        from_hilbert_indices(&src, level, &mut points);
+        for (h, p) in src.iter().zip(points.iter()) {
+            assert_eq!(*p, h.from_hilbert_index(level));
+        }
+
+        let mut hindices = vec![0; src.len()];
+        to_hilbert_indices(&points, level, &mut hindices);
+        for (p, h) in points.iter().zip(hindices.iter()) {
+            assert_eq!(*h, p.to_hilbert_index(level));
+        }
+        assert_eq!(hindices, src);
+    }
+
+    #[test]
+    #[should_panic(expected = "src has 2 elements, but dst has 3")]
+    fn length_mismatch() {
+        let mut points = [[0; 2]; 3];
+        from_hilbert_indices(&[0, 1], 1, &mut points);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{reverse_digits, FromHilbertIndex, ToHilbertIndex};
+
+/// The order of the words of a Hilbert index, i.e. where the coarsest level of the recursion is placed.
+/// 
+/// A Hilbert index consists of `level` words of `D` bits, one for each level of the recursion.
+/// This crate places the word of the coarsest level at the highest bits (`BigEndian`, the default),
+/// so that the indices are sorted along the curve;
+/// some external formats nest them the other way around (`LittleEndian`).
+/// The two are related by `reverse_digits`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_ordered, reverse_digits, to_hilbert_index_ordered, BitOrder, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// let h = [1, 2].to_hilbert_index(level);
+/// assert_eq!( h, to_hilbert_index_ordered(&[1, 2], level, BitOrder::default()) );
+/// 
+/// let le = to_hilbert_index_ordered(&[1, 2], level, BitOrder::LittleEndian);
+/// assert_eq!( reverse_digits::<2>(h, level), le );
+/// assert_eq!( [1, 2], from_hilbert_index_ordered(le, level, BitOrder::LittleEndian) );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitOrder {
+    /// The word of the coarsest level is at the highest bits, as built by `h = (h << D) | w` (default).
+    #[default]
+    BigEndian,
+    /// The word of the coarsest level is at the lowest bits.
+    LittleEndian,
+}
+
+/// Convert a grid point to a Hilbert index whose words are placed in the given `BitOrder`.
+/// 
+/// With `BitOrder::BigEndian` this is `p.to_hilbert_index(level)`.
+pub fn to_hilbert_index_ordered<const D: usize>(p: &[usize; D], level: usize, order: BitOrder) -> usize {
+    let h = p.to_hilbert_index(level);
+    match order {
+        BitOrder::BigEndian => h,
+        BitOrder::LittleEndian => reverse_digits::<D>(h, level),
+    }
+}
+
+/// Convert a Hilbert index whose words are placed in the given `BitOrder` to a grid point.
+/// 
+/// This is the inverse of `to_hilbert_index_ordered`.
+/// With `BitOrder::BigEndian` this is `h.from_hilbert_index(level)`.
+pub fn from_hilbert_index_ordered<const D: usize>(h: usize, level: usize, order: BitOrder) -> [usize; D] {
+    let h = match order {
+        BitOrder::BigEndian => h,
+        BitOrder::LittleEndian => reverse_digits::<D>(h, level),
+    };
+    h.from_hilbert_index(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_ordered, points, reverse_digits, to_hilbert_index_ordered, BitOrder, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        for p in points::<D>(level) {
+            let h = p.to_hilbert_index(level);
+            assert_eq!(to_hilbert_index_ordered(&p, level, BitOrder::default()), h);
+            let le = to_hilbert_index_ordered(&p, level, BitOrder::LittleEndian);
+            assert_eq!(le, reverse_digits::<D>(h, level));
+            assert_eq!(from_hilbert_index_ordered::<D>(le, level, BitOrder::LittleEndian), p);
+            assert_eq!(from_hilbert_index_ordered::<D>(h, level, BitOrder::BigEndian), p);
+        }
+    }
+
+    #[test]
+    fn orders() {
+        assert_eq!(BitOrder::default(), BitOrder::BigEndian);
+        check::<2>(0);
+        check::<2>(3);
+        check::<3>(2);
+        check::<5>(2);
+
+        // level 1 では語が 1 つなので同じ
+        assert_eq!(to_hilbert_index_ordered(&[1, 1], 1, BitOrder::LittleEndian), 2);
+        // [2, 0] は level 1 で [1, 0] (添字 3) の部分格子の中で 2 番目
+        assert_eq!([2, 0].to_hilbert_index(2), 0b11_10);
+        assert_eq!(to_hilbert_index_ordered(&[2, 0], 2, BitOrder::LittleEndian), 0b10_11);
+    }
+}
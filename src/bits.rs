@@ -0,0 +1,102 @@
+/// The mask `2.pow(D) - 1` of a `D`-bit word, such as a bit plane or a word of a Hilbert index.
+/// 
+/// For `D >= usize::BITS` this is `usize::MAX` instead of shifting by `D` or more bits,
+/// so it is defined for any `D` and can be used in constants.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::dimension_mask;
+/// 
+/// assert_eq!( 0b111, dimension_mask::<3>() );
+/// assert_eq!( usize::MAX, dimension_mask::<{ usize::BITS as usize }>() );
+/// const MASK: usize = dimension_mask::<2>();
+/// assert_eq!( 0b10, 6 & MASK );
+/// ```
+/// 
+#[inline]
+pub const fn dimension_mask<const D: usize>() -> usize {
+    if D >= usize::BITS as usize { usize::MAX } else { !( {usize::MAX}<<D ) }
+}
+
+/// Extract the `i`-th bit of every component of `p` into a `D`-bit word (a bit plane).
+/// 
+/// The `k`-th bit of the result is the `i`-th bit of `p[k]`.
+/// The Hilbert curve processes the bit planes from the most significant one (`i = level - 1`) to the least one (`i = 0`),
+/// and the Morton (Z-order) index is the concatenation of the bit planes.
+/// The inverse is `scatter_bit_plane`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::extract_bit_plane;
+/// 
+/// // the lowest bits of 0b01, 0b10 and 0b11 are 1, 0 and 1
+/// assert_eq!( 0b101, extract_bit_plane(&[0b01, 0b10, 0b11], 0) );
+/// assert_eq!( 0b110, extract_bit_plane(&[0b01, 0b10, 0b11], 1) );
+/// ```
+/// 
+#[inline]
+pub fn extract_bit_plane<const D: usize>(p: &[usize; D], i: usize) -> usize {
+    p.iter().enumerate()
+        .fold(0, |l, (k, p)| l^( ((p >> i)&1) << k))
+}
+
+/// Write a `D`-bit word into the `i`-th bit of every component of `p`.
+/// 
+/// The `i`-th bit of `p[k]` is set to the `k`-th bit of `word`, and the other bits of `p` are kept.
+/// This is the inverse of `extract_bit_plane`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{extract_bit_plane, scatter_bit_plane};
+/// 
+/// let mut p = [0b10, 0b10, 0b11];
+/// scatter_bit_plane(0b101, 0, &mut p);
+/// assert_eq!( [0b11, 0b10, 0b11], p );
+/// assert_eq!( 0b101, extract_bit_plane(&p, 0) );
+/// ```
+/// 
+#[inline]
+pub fn scatter_bit_plane<const D: usize>(word: usize, i: usize, p: &mut [usize; D]) {
+    for (k, x) in p.iter_mut().enumerate() {
+        *x = (*x & !(1 << i)) | (((word >> k)&1) << i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dimension_mask, extract_bit_plane, points, scatter_bit_plane};
+
+    #[test]
+    fn mask() {
+        assert_eq!(dimension_mask::<0>(), 0);
+        assert_eq!(dimension_mask::<1>(), 1);
+        assert_eq!(dimension_mask::<3>(), 0b111);
+        assert_eq!(dimension_mask::<{ usize::BITS as usize - 1 }>(), usize::MAX >> 1);
+        assert_eq!(dimension_mask::<{ usize::BITS as usize }>(), usize::MAX);
+        assert_eq!(dimension_mask::<200>(), usize::MAX);
+        const M: usize = dimension_mask::<1000>();
+        assert_eq!(M, usize::MAX);
+        assert_eq!(dimension_mask::<5>(), crate::max::<5>());
+    }
+
+    #[test]
+    fn inverse() {
+        let level = 3;
+        for p in points::<3>(level) {
+            let mut q = [0; 3];
+            for i in 0..level {
+                scatter_bit_plane(extract_bit_plane(&p, i), i, &mut q);
+            }
+            assert_eq!(p, q);
+        }
+
+        for word in 0..16 {
+            let mut p = [usize::MAX, 0, 0b1010, 7];
+            scatter_bit_plane(word, 2, &mut p);
+            assert_eq!(extract_bit_plane(&p, 2), word);
+        }
+    }
+}
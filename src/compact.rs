@@ -0,0 +1,100 @@
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// Convert a Hilbert index in the cube of `cube_level` to the compact Hilbert index of the same grid point.
+/// 
+/// The index is decoded by `from_hilbert_index(cube_level)` and encoded again by `to_compact_hilbert_index(compact_levels)`.
+/// If `compact_levels[k] < cube_level` for some axis, the conversion is lossy:
+/// the `k`-th component is taken modulo `2.pow(compact_levels[k])`, i.e. its high bits are dropped,
+/// so the point may not be recovered by `compact_to_cube`.
+/// If `compact_levels == [cube_level; D]`, the index is unchanged.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{compact_to_cube, cube_to_compact, ToHilbertIndex};
+/// 
+/// let h = [3, 1].to_hilbert_index(2);
+/// let c = cube_to_compact(h, 2, [2, 1]);
+/// assert_eq!( [3, 1].to_compact_hilbert_index([2, 1]), c );
+/// assert_eq!( h, compact_to_cube(c, [2, 1], 2) );
+/// ```
+/// 
+pub fn cube_to_compact<const D: usize>(h: usize, cube_level: usize, compact_levels: [usize; D]) -> usize {
+    let mut p: [usize; D] = h.from_hilbert_index(cube_level);
+    for (x, &l) in p.iter_mut().zip(compact_levels.iter()) {
+        *x &= low_bits(l);
+    }
+    p.to_compact_hilbert_index(compact_levels)
+}
+
+/// Convert a compact Hilbert index to the Hilbert index of the same grid point in the cube of `cube_level`.
+/// 
+/// This is the inverse of `cube_to_compact`. It is lossless if `compact_levels[k] <= cube_level` for every axis;
+/// otherwise the components are taken modulo `2.pow(cube_level)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{compact_to_cube, ToHilbertIndex};
+/// 
+/// let levels = [2, 1];
+/// let c = [3, 1].to_compact_hilbert_index(levels);
+/// assert_eq!( [3, 1].to_hilbert_index(2), compact_to_cube(c, levels, 2) );
+/// ```
+/// 
+pub fn compact_to_cube<const D: usize>(h: usize, compact_levels: [usize; D], cube_level: usize) -> usize {
+    let mut p: [usize; D] = h.from_compact_hilbert_index(compact_levels);
+    for x in p.iter_mut() {
+        *x &= low_bits(cube_level);
+    }
+    p.to_hilbert_index(cube_level)
+}
+
+// 下位 l bit のマスク
+#[inline]
+fn low_bits(l: usize) -> usize {
+    !( {usize::MAX}.checked_shl(l as u32).unwrap_or(0) )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compact_to_cube, cube_to_compact, indices, FromHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn equal_levels() {
+        for level in 0..4 {
+            for h in indices::<3>(level) {
+                assert_eq!(cube_to_compact(h, level, [level; 3]), h);
+                assert_eq!(compact_to_cube(h, [level; 3], level), h);
+            }
+        }
+    }
+
+    #[test]
+    fn lossless() {
+        let levels = [3, 1, 2];
+        for c in 0..1 << 6 {
+            let h = compact_to_cube(c, levels, 3);
+            assert_eq!(h, c.from_compact_hilbert_index(levels).to_hilbert_index(3));
+            assert_eq!(cube_to_compact(h, 3, levels), c);
+        }
+    }
+
+    #[test]
+    fn lossy() {
+        let (level, levels) = (3, [3, 1]);
+        for h in indices::<2>(level) {
+            let [x, y] = h.from_hilbert_index(level);
+            let c = cube_to_compact(h, level, levels);
+            assert_eq!(c, [x, y & 1].to_compact_hilbert_index(levels));
+            assert_eq!(compact_to_cube(c, levels, level), [x, y & 1].to_hilbert_index(level));
+        }
+
+        // compact の方が細かい場合
+        let (level, levels) = (1, [3, 1]);
+        for c in 0..1 << 4 {
+            let [x, y] = c.from_compact_hilbert_index(levels);
+            assert_eq!(compact_to_cube(c, levels, level), [x & 1, y].to_hilbert_index(level));
+        }
+    }
+}
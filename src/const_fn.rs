@@ -0,0 +1,136 @@
+use crate::{dmap, emap, gray, max, max_level, t, t_inv};
+
+/// `const` version of `ToHilbertIndex::to_hilbert_index`.
+/// 
+/// This can be evaluated at compile time, e.g. to build a lookup table in a `const` item.
+/// At runtime it is slower than `to_hilbert_index`, which uses state-transition tables for `D = 2, 3`.
+/// 
+/// # Panics
+/// 
+/// Panics (or fails to compile in a `const` context) if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::to_hilbert_index_const;
+/// 
+/// const H: usize = to_hilbert_index_const([1, 1], 1);
+/// assert_eq!( 2, H );
+/// ```
+/// 
+pub const fn to_hilbert_index_const<const D: usize>(p: [usize; D], level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level exceeds max_level");
+    let (mut h, mut e, mut d) = (0, 0, 0);
+    let mut i = level;
+    while i > 0 {
+        i -= 1;
+        let mut b = 0;
+        let mut k = 0;
+        while k < D {
+            b |= ((p[k] >> i)&1) << k;
+            k += 1;
+        }
+        let w = gray::decode::<D>(t::<D>(b, e, d));
+        e ^= gray::rotate_left::<D>(emap(w), d+1);
+        d = ( d + dmap::<D>(w) + 1 )%D;
+        h = (h << D) | w;
+    }
+
+    h
+}
+
+/// `const` version of `FromHilbertIndex::from_hilbert_index`.
+/// 
+/// # Panics
+/// 
+/// Panics (or fails to compile in a `const` context) if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::from_hilbert_index_const;
+/// 
+/// // grid points of the 2-dimensional curve of level 3, computed at compile time
+/// const TABLE: [[usize; 2]; 64] = {
+///     let mut table = [[0; 2]; 64];
+///     let mut h = 0;
+///     while h < 64 {
+///         table[h] = from_hilbert_index_const(h, 3);
+///         h += 1;
+///     }
+///     table
+/// };
+/// assert_eq!( [1, 1], TABLE[2] );
+/// ```
+/// 
+pub const fn from_hilbert_index_const<const D: usize>(h: usize, level: usize) -> [usize; D] {
+    assert!(level <= max_level::<D>(), "level exceeds max_level");
+    let (mut e, mut d) = (0, 0);
+    let mut p = [0; D];
+    let mut i = level;
+    while i > 0 {
+        i -= 1;
+        let w = (h >> (i*D)) & max::<D>();
+        let l = t_inv::<D>(gray::encode(w), e, d);
+        let mut k = 0;
+        while k < D {
+            p[k] = (p[k] << 1)|((l >> k)&1);
+            k += 1;
+        }
+        e ^= gray::rotate_left::<D>(emap(w), d+1);
+        d = ( d + dmap::<D>(w) + 1 )%D;
+    }
+
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_const, indices, to_hilbert_index_const, FromHilbertIndex, ToHilbertIndex};
+
+    const LEVEL: usize = 3;
+    const TABLE: [[usize; 2]; 64] = {
+        let mut table = [[0; 2]; 64];
+        let mut h = 0;
+        while h < 64 {
+            table[h] = from_hilbert_index_const(h, LEVEL);
+            h += 1;
+        }
+        table
+    };
+    const INVERSE: [usize; 64] = {
+        let mut inverse = [0; 64];
+        let mut h = 0;
+        while h < 64 {
+            inverse[h] = to_hilbert_index_const(TABLE[h], LEVEL);
+            h += 1;
+        }
+        inverse
+    };
+
+    #[test]
+    fn compile_time_table() {
+        for h in indices::<2>(LEVEL) {
+            assert_eq!(TABLE[h], h.from_hilbert_index(LEVEL));
+            assert_eq!(INVERSE[h], h);
+        }
+    }
+
+    fn check<const D: usize>(level: usize) {
+        for h in indices::<D>(level) {
+            let p: [usize; D] = h.from_hilbert_index(level);
+            assert_eq!(from_hilbert_index_const::<D>(h, level), p);
+            assert_eq!(to_hilbert_index_const(p, level), p.to_hilbert_index(level));
+        }
+    }
+
+    #[test]
+    fn same_as_traits() {
+        for level in 0..5 {
+            check::<2>(level);
+            check::<3>(level);
+            check::<4>(level);
+        }
+        check::<7>(2);
+    }
+}
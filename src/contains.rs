@@ -0,0 +1,85 @@
+use crate::{dmap, emap, gray, t_inv};
+
+/// Test whether the grid point of a Hilbert index `h` is inside an axis-aligned box.
+/// 
+/// The box is given as in `box_to_ranges`, i.e. `min[k] <= x[k] <= max[k]` for every axis `k` (both ends inclusive),
+/// so `min == max` is the box of the single point and `min[k] > max[k]` is the empty box.
+/// This is `h.from_hilbert_index(level)` followed by the componentwise check, without allocation,
+/// but the index is decoded from the coarsest level and the test stops as soon as the cell of `h`
+/// gets out of the box on some axis, or gets inside the box entirely.
+/// Use this to filter a few indices, and `box_to_ranges` to query many of them.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{box_contains_index, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// let (min, max) = ([1, 0], [2, 3]);
+/// assert!( box_contains_index(min, max, level, [1, 3].to_hilbert_index(level)) );
+/// assert!( !box_contains_index(min, max, level, [3, 3].to_hilbert_index(level)) );
+/// ```
+/// 
+pub fn box_contains_index<const D: usize>(min: [usize; D], max: [usize; D], level: usize, h: usize) -> bool {
+    // corner は level i の格子の最小の角
+    let mut corner = [0; D];
+    let (mut e, mut d) = (0, 0);
+    for i in (0..level).rev() {
+        let w = (h >> (i*D)) & crate::max::<D>();
+        let l = t_inv::<D>(gray::encode(w), e, d);
+        let mut inside = true;
+        for (k, x) in corner.iter_mut().enumerate() {
+            *x |= ((l >> k)&1) << i;
+            let (lo, hi) = (*x, *x + ((1 << i) - 1));
+            if hi < min[k] || max[k] < lo { return false; }
+            inside &= min[k] <= lo && hi <= max[k];
+        }
+        if inside { return true; }
+        e ^= gray::rotate_left::<D>(emap(w), d+1);
+        d = ( d + dmap::<D>(w) + 1 )%D;
+    }
+    // level = 0 の場合のみここに到達する. 格子点は [0; D] だけ.
+    min.iter().all(|&a| a == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{box_contains_index, indices, points, FromHilbertIndex};
+
+    fn naive<const D: usize>(min: [usize; D], max: [usize; D], level: usize, h: usize) -> bool {
+        let p: [usize; D] = h.from_hilbert_index(level);
+        (0..D).all(|k| min[k] <= p[k] && p[k] <= max[k])
+    }
+
+    #[test]
+    fn same_as_decode() {
+        let level = 2;
+        for min in points::<2>(level) {
+            for max in points::<2>(level) {
+                for h in indices::<2>(level) {
+                    assert_eq!(box_contains_index(min, max, level, h), naive(min, max, level, h), "{:?} {:?} {}", min, max, h);
+                }
+            }
+        }
+
+        let level = 3;
+        for (min, max) in [([1, 2, 0], [6, 3, 7]), ([0, 0, 0], [7, 7, 7]), ([4, 4, 4], [3, 7, 7]), ([5, 0, 2], [5, 7, 2])] {
+            for h in indices::<3>(level) {
+                assert_eq!(box_contains_index(min, max, level, h), naive(min, max, level, h));
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_box() {
+        let level = 3;
+        for p in points::<3>(level) {
+            for h in indices::<3>(level) {
+                let q: [usize; 3] = h.from_hilbert_index(level);
+                assert_eq!(box_contains_index(p, p, level, h), p == q);
+            }
+        }
+        assert!(box_contains_index([0, 0], [0, 0], 0, 0));
+        assert!(!box_contains_index([1, 0], [1, 0], 0, 0));
+    }
+}
@@ -0,0 +1,85 @@
+/// Scalar types of the components of a grid point, such as unit-typed newtypes.
+/// 
+/// `ToHilbertIndex` is implemented for `[C; D]` with any `C: Coordinate`,
+/// and `FromHilbertIndex::from_hilbert_index_coords` decodes into `[C; D]`,
+/// so that the type of the coordinates is kept end-to-end.
+/// `[usize; D]` is the instance with `C = usize`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{Coordinate, FromHilbertIndex, ToHilbertIndex};
+/// 
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Meters(u32);
+/// 
+/// impl Coordinate for Meters {
+///     fn as_grid(&self) -> usize { self.0 as usize }
+///     fn from_grid(g: usize) -> Self { Meters(g as u32) }
+/// }
+/// 
+/// let level = 2;
+/// let h = [Meters(3), Meters(1)].to_hilbert_index(level);
+/// assert_eq!( [3, 1].to_hilbert_index(level), h );
+/// assert_eq!( [Meters(3), Meters(1)], h.from_hilbert_index_coords(level) );
+/// ```
+/// 
+pub trait Coordinate: Sized {
+    /// The grid coordinate of `self`, which must be less than `2.pow(level)` for the level in use.
+    fn as_grid(&self) -> usize;
+
+    /// The value of a grid coordinate. This is the inverse of `as_grid`.
+    fn from_grid(g: usize) -> Self;
+}
+
+impl Coordinate for usize {
+    #[inline]
+    fn as_grid(&self) -> usize { *self }
+
+    #[inline]
+    fn from_grid(g: usize) -> Self { g }
+}
+
+// [C; D] を格子点 [usize; D] に変換する
+#[inline]
+pub(crate) fn to_grid<C: Coordinate, const D: usize>(p: &[C; D]) -> [usize; D] {
+    let mut q = [0; D];
+    for (x, c) in q.iter_mut().zip(p.iter()) {
+        *x = c.as_grid();
+    }
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{points, Coordinate, FromHilbertIndex, ToHilbertIndex};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Meters(u32);
+
+    impl Coordinate for Meters {
+        fn as_grid(&self) -> usize { self.0 as usize }
+        fn from_grid(g: usize) -> Self { Meters(g as u32) }
+    }
+
+    #[test]
+    fn round_trip() {
+        fn check<const D: usize>(level: usize) {
+            for p in points::<D>(level) {
+                let q = p.map(|x| Meters(x as u32));
+                let h = q.to_hilbert_index(level);
+                assert_eq!(h, p.to_hilbert_index(level));
+                assert_eq!(h.from_hilbert_index_coords::<Meters>(level), q);
+                assert_eq!(q.to_hilbert_index_with(level, 1, 0), p.to_hilbert_index_with(level, 1, 0));
+                assert_eq!(q.try_to_hilbert_index(level), Ok(h));
+            }
+        }
+        check::<2>(3);
+        check::<3>(2);
+        check::<4>(2);
+
+        let levels = [2, 1];
+        assert_eq!([Meters(3), Meters(1)].to_compact_hilbert_index(levels), [3, 1].to_compact_hilbert_index(levels));
+        assert!([Meters(4), Meters(0)].try_to_hilbert_index(2).is_err());
+    }
+}
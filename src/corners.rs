@@ -0,0 +1,188 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use crate::iter::last;
+use crate::{max_level, num_cells, FromHilbertIndex};
+#[cfg(feature = "alloc")]
+use crate::ToHilbertIndex;
+
+/// The first grid point of the curve, i.e. the grid point of the Hilbert index `0`.
+/// 
+/// This is always the origin `[0; D]`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::first_point;
+/// 
+/// assert_eq!( [0, 0, 0], first_point::<3>(4) );
+/// ```
+/// 
+pub fn first_point<const D: usize>(level: usize) -> [usize; D] {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    [0; D]
+}
+
+/// The last grid point of the curve, i.e. the grid point of the Hilbert index `2.pow(D*level) - 1`.
+/// 
+/// This is the corner `[2.pow(level) - 1, 0, .., 0]`, adjacent to the origin along the first axis at `level = 1`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::last_point;
+/// 
+/// assert_eq!( [1, 0], last_point::<2>(1) );
+/// assert_eq!( [7, 0, 0], last_point::<3>(3) );
+/// ```
+/// 
+pub fn last_point<const D: usize>(level: usize) -> [usize; D] {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    last::<D>(level).from_hilbert_index(level)
+}
+
+/// The grid point in the middle of the curve, i.e. the grid point of the Hilbert index `2.pow(D*level) / 2`.
+/// 
+/// The curve up to (but excluding) this point and the curve from this point have the same number of cells,
+/// so this splits the box into two halves of good locality.
+/// For `level = 0` this is the single point `[0; D]`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{midpoint_point, FromHilbertIndex};
+/// 
+/// let level = 2;
+/// assert_eq!( 8.from_hilbert_index(level), midpoint_point::<2>(level) );
+/// assert_eq!( [2, 2], midpoint_point::<2>(level) );
+/// ```
+/// 
+pub fn midpoint_point<const D: usize>(level: usize) -> [usize; D] {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    (num_cells::<D>(level)/2).from_hilbert_index(level)
+}
+
+/// The grid point at a fraction `f` of the curve, i.e. the grid point of the Hilbert index `floor(f * 2.pow(D*level))`.
+/// 
+/// `f = 0.0` gives the first point, `f = 0.5` gives `midpoint_point`, and `f = 1.0` gives the last point
+/// (the index is clamped to `2.pow(D*level) - 1`).
+/// `f` outside `[0, 1]` is clamped and `NaN` is treated as `0.0`, as in `from_unit_coords`.
+/// The index is computed with `f64`, so it may be off by a small relative error for large `D*level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{first_point, last_point, midpoint_point, point_at_fraction, FromHilbertIndex};
+/// 
+/// let level = 3;
+/// assert_eq!( first_point::<2>(level), point_at_fraction(level, 0.0) );
+/// assert_eq!( midpoint_point::<2>(level), point_at_fraction(level, 0.5) );
+/// assert_eq!( last_point::<2>(level), point_at_fraction(level, 1.0) );
+/// assert_eq!( 16.from_hilbert_index(level), point_at_fraction::<2>(level, 0.25) );
+/// ```
+/// 
+pub fn point_at_fraction<const D: usize>(level: usize, f: f64) -> [usize; D] {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    let n = num_cells::<D>(level);
+    // `as usize` は負の値と NaN を 0 に飽和させる
+    let h = (f * n as f64) as usize;
+    h.min(n - 1).from_hilbert_index(level)
+}
+
+/// The `2.pow(D)` corners of the grid box with their Hilbert indices `(h, p)`, sorted by `h` (requires the `alloc` feature).
+/// 
+/// The `k`-th component of a corner is `0` or `2.pow(level) - 1`.
+/// For `level = 0` all corners are the single point `[0; D]`, which is returned once.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::corner_points;
+/// 
+/// let corners = corner_points::<2>(2);
+/// assert_eq!( vec![(0, [0, 0]), (5, [0, 3]), (10, [3, 3]), (15, [3, 0])], corners );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn corner_points<const D: usize>(level: usize) -> Vec<(usize, [usize; D])> {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    if level == 0 { return alloc::vec![(0, [0; D])]; }
+
+    let side = (1 << level) - 1;
+    let mut corners: Vec<(usize, [usize; D])> = (0..1usize << D)
+        .map(|c| {
+            let mut p = [0; D];
+            for (k, x) in p.iter_mut().enumerate() {
+                *x = side*((c >> k)&1);
+            }
+            (p.to_hilbert_index(level), p)
+        })
+        .collect();
+    corners.sort_unstable();
+    corners
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{first_point, last_point, midpoint_point, point_at_fraction, FromHilbertIndex};
+
+    #[test]
+    fn endpoints() {
+        assert_eq!(last_point::<2>(1), [1, 0]);
+        assert_eq!(first_point::<2>(1), [0, 0]);
+        for level in 0..5 {
+            let n = 1usize << (3*level);
+            assert_eq!(first_point::<3>(level), 0.from_hilbert_index(level));
+            assert_eq!(last_point::<3>(level), (n - 1).from_hilbert_index(level));
+        }
+        assert_eq!(last_point::<5>(2), [3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn midpoint() {
+        assert_eq!(midpoint_point::<2>(2), 8.from_hilbert_index(2));
+        assert_eq!(midpoint_point::<2>(0), [0, 0]);
+        assert_eq!(midpoint_point::<1>(3), [4]);
+        for level in 1..5 {
+            let n = 1usize << (3*level);
+            assert_eq!(midpoint_point::<3>(level), (n/2).from_hilbert_index(level));
+        }
+    }
+
+    #[test]
+    fn fraction_boundaries() {
+        let level = 2;
+        let at = |h: usize| -> [usize; 2] { h.from_hilbert_index(level) };
+        assert_eq!(point_at_fraction(level, 0.0), at(0));
+        assert_eq!(point_at_fraction(level, 1.0/16.0), at(1));
+        assert_eq!(point_at_fraction(level, 1.0/16.0 - 1e-9), at(0));
+        assert_eq!(point_at_fraction(level, 0.5), at(8));
+        assert_eq!(point_at_fraction(level, 15.0/16.0), at(15));
+        assert_eq!(point_at_fraction(level, 1.0 - 1e-9), at(15));
+        assert_eq!(point_at_fraction(level, 1.0), at(15));
+        // 範囲外は端に寄せる
+        assert_eq!(point_at_fraction(level, -0.5), at(0));
+        assert_eq!(point_at_fraction(level, 2.0), at(15));
+        assert_eq!(point_at_fraction(level, f64::NAN), at(0));
+        assert_eq!(point_at_fraction::<3>(0, 0.7), [0, 0, 0]);
+        assert_eq!(point_at_fraction::<2>(31, 1.0), last_point(31));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn corners() {
+        use crate::corner_points;
+
+        assert_eq!(corner_points::<3>(0), vec![(0, [0, 0, 0])]);
+        for level in 1..4 {
+            let corners = corner_points::<3>(level);
+            assert_eq!(corners.len(), 8);
+            assert_eq!(corners[0], (0, first_point(level)));
+            assert_eq!(corners[7], ((1 << (3*level)) - 1, last_point(level)));
+            for &(h, p) in corners.iter() {
+                assert_eq!(p, h.from_hilbert_index(level));
+                assert!(p.iter().all(|&x| x == 0 || x == (1 << level) - 1));
+            }
+            assert!(corners.windows(2).all(|w| w[0].0 < w[1].0));
+        }
+    }
+}
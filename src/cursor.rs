@@ -0,0 +1,143 @@
+use crate::iter::{last, Walker};
+use crate::max_level;
+
+/// A cursor on the Hilbert curve, which moves back and forth along the curve and keeps the current grid point.
+/// 
+/// This is the mutable counterpart of `points`.
+/// The grid point is updated incrementally as `points` does:
+/// a step recomputes only the levels whose words of the index change,
+/// which is `O(1)` on average, and exactly one component of the point changes by `1`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertCursor;
+/// 
+/// let level = 1;
+/// let mut cursor = HilbertCursor::<2>::new(level);
+/// assert_eq!( (0, [0, 0]), (cursor.index(), cursor.point()) );
+/// 
+/// assert!( cursor.advance() );
+/// assert!( cursor.advance() );
+/// assert_eq!( (2, [1, 1]), (cursor.index(), cursor.point()) );
+/// 
+/// assert!( cursor.retreat() );
+/// assert_eq!( (1, [0, 1]), (cursor.index(), cursor.point()) );
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct HilbertCursor<const D: usize> {
+    walker: Walker<D>,
+    level: usize,
+    index: usize,
+}
+
+impl<const D: usize> HilbertCursor<D> {
+    /// Create a cursor at the first point `[0; D]` (index `0`) of the curve of a given level.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `level > max_level::<D>()`.
+    pub fn new(level: usize) -> Self {
+        Self::at(0, level)
+    }
+
+    /// Create a cursor at the point of index `h`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `level > max_level::<D>()` or `h >= 2.pow(D*level)`.
+    pub fn at(h: usize, level: usize) -> Self {
+        assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        assert!(h <= last::<D>(level), "index {} is out of the curve of level {}", h, level);
+        HilbertCursor { walker: Walker::new(h, level), level, index: h }
+    }
+
+    /// The level of the curve.
+    #[inline]
+    pub fn level(&self) -> usize { self.level }
+
+    /// The Hilbert index of the current point.
+    #[inline]
+    pub fn index(&self) -> usize { self.index }
+
+    /// The current grid point, i.e. `self.index().from_hilbert_index(self.level())`.
+    #[inline]
+    pub fn point(&self) -> [usize; D] { self.walker.p }
+
+    /// Move to the next point along the curve.
+    /// 
+    /// Returns `false` and stays at the same point if the cursor is at the last point.
+    pub fn advance(&mut self) -> bool {
+        if self.index == last::<D>(self.level) { return false; }
+        self.seek(self.index + 1);
+        true
+    }
+
+    /// Move to the previous point along the curve.
+    /// 
+    /// Returns `false` and stays at the same point if the cursor is at the first point.
+    pub fn retreat(&mut self) -> bool {
+        if self.index == 0 { return false; }
+        self.seek(self.index - 1);
+        true
+    }
+
+    /// Move to the point of index `h`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `h >= 2.pow(D*level)`.
+    pub fn seek(&mut self, h: usize) {
+        assert!(h <= last::<D>(self.level), "index {} is out of the curve of level {}", h, self.level);
+        self.walker.seek(h);
+        self.index = h;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromHilbertIndex, HilbertCursor};
+
+    fn check<const D: usize>(level: usize) {
+        let mut cursor = HilbertCursor::<D>::new(level);
+        let n = 1 << (D*level);
+        for k in 0..n {
+            let p: [usize; D] = k.from_hilbert_index(level);
+            assert_eq!((cursor.index(), cursor.point()), (k, p));
+            assert_eq!(cursor.advance(), k + 1 < n);
+        }
+        assert_eq!(cursor.index(), n - 1);
+        for k in (0..n).rev() {
+            let p: [usize; D] = k.from_hilbert_index(level);
+            assert_eq!((cursor.index(), cursor.point()), (k, p));
+            assert_eq!(cursor.retreat(), k > 0);
+        }
+        assert_eq!(cursor.index(), 0);
+    }
+
+    #[test]
+    fn advance_and_retreat() {
+        check::<1>(4);
+        check::<2>(0);
+        check::<2>(3);
+        check::<3>(2);
+        check::<4>(2);
+    }
+
+    #[test]
+    fn seek() {
+        let level = 3;
+        let mut cursor = HilbertCursor::<3>::at(100, level);
+        assert_eq!(cursor.point(), 100.from_hilbert_index(level));
+        cursor.seek(7);
+        assert!(cursor.advance());
+        assert_eq!((cursor.index(), cursor.point()), (8, 8.from_hilbert_index(level)));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of the curve")]
+    fn seek_out_of_curve() {
+        HilbertCursor::<2>::new(2).seek(16);
+    }
+}
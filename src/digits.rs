@@ -0,0 +1,209 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+// 添字の下から i 番目 (0-origin) の D bit の語
+#[inline]
+fn digit<const D: usize>(h: usize, i: usize) -> usize {
+    (h >> (i*D)) & !( {usize::MAX}<<D )
+}
+
+/// Split a Hilbert index into its `level` words of `D` bits (requires the `alloc` feature).
+/// 
+/// The words are returned from the most significant one (the coarsest level) to the least one,
+/// i.e., in the order consumed by the curve recursion; each word is the position of the sub-cell
+/// among the `2^D` children of the current cell.
+/// Bits of `h` above `D*level` are ignored. The inverse is `from_digits`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_digits, index_digits};
+/// 
+/// let digits = index_digits::<3>(0b101_011_000, 3);
+/// assert_eq!( vec![0b101, 0b011, 0b000], digits );
+/// assert_eq!( 0b101_011_000, from_digits::<3>(&digits) );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn index_digits<const D: usize>(h: usize, level: usize) -> Vec<usize> {
+    (0..level).rev().map(|i| digit::<D>(h, i)).collect()
+}
+
+/// The `D`-bit word of a Hilbert index selected at a given depth of the recursion, where `0` is the coarsest.
+/// 
+/// The word is the position, in the Hilbert order, of the sub-cell containing the cell `h`
+/// among the `2^D` children of its ancestor at level `depth`,
+/// i.e. `index_digits::<D>(h, level)[depth]`.
+/// In particular the word at `depth = 0` is the index of the coarsest sub-cube at level `1`,
+/// so the words at depths `0..=m` form the index of the enclosing cell at level `m+1`.
+/// This is useful for overlaying a quadtree or an octree on the curve.
+/// 
+/// # Panics
+/// 
+/// Panics if `depth >= level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{subcube_at_depth, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let h = [6, 1].to_hilbert_index(level);
+/// // [6, 1] is in the lower-right quadrant [1, 0], the last one of the curve at level 1
+/// assert_eq!( 3, subcube_at_depth::<2>(h, level, 0) );
+/// assert_eq!( 0b11_11_01, h );
+/// assert_eq!( 0b01, subcube_at_depth::<2>(h, level, 2) );
+/// ```
+/// 
+pub fn subcube_at_depth<const D: usize>(h: usize, level: usize, depth: usize) -> usize {
+    assert!(depth < level, "depth {} must be less than the level {}", depth, level);
+    digit::<D>(h, level - 1 - depth)
+}
+
+/// Assemble a Hilbert index from its `D`-bit words, given from the most significant one.
+/// 
+/// This is the inverse of `index_digits`, and the level is `digits.len()`.
+/// 
+/// # Panics
+/// 
+/// Panics in debug mode if a word is not less than `2^D`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_digits, FromHilbertIndex};
+/// 
+/// let h = from_digits::<2>(&[0b10, 0b01]);
+/// assert_eq!( 9, h );
+/// assert_eq!( [2, 3], h.from_hilbert_index(2) );
+/// ```
+/// 
+pub fn from_digits<const D: usize>(digits: &[usize]) -> usize {
+    digits.iter().fold(0, |h, &w| {
+        debug_assert!(w >> D == 0, "from_digits: word {:#b} has more than {} bits", w, D);
+        (h << D) | w
+    })
+}
+
+/// Reverse the order of the `level` words of `D` bits of a Hilbert index.
+/// 
+/// The word of the coarsest level moves from the highest bits to the lowest ones and vice versa,
+/// i.e. `index_digits` of the result is the reverse of `index_digits` of `h`.
+/// This converts between the two `BitOrder`s, and is an involution.
+/// Bits of `h` above `D*level` are ignored.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::reverse_digits;
+/// 
+/// assert_eq!( 0b000_011_101, reverse_digits::<3>(0b101_011_000, 3) );
+/// assert_eq!( 0b01_10, reverse_digits::<2>(0b10_01, 2) );
+/// ```
+/// 
+pub fn reverse_digits<const D: usize>(h: usize, level: usize) -> usize {
+    (0..level).fold(0, |r, i| (r << D) | digit::<D>(h, i))
+}
+
+/// Format a Hilbert index as its `level` words of `D` bits (requires the `alloc` feature).
+/// 
+/// The index is the concatenation `w_level ... w_2 w_1` of `D`-bit words,
+/// where `w_level` is consumed at the coarsest level of the recursion and `w_1` at the finest,
+/// and each word is printed as `w{i}={binary}` from the most significant one, separated by `|`.
+/// This is useful to follow the algorithm by hand.
+/// Bits of `h` above `D*level` are ignored.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::format_index;
+/// 
+/// assert_eq!( "w3=101|w2=011|w1=000", format_index::<3>(0b101_011_000, 3) );
+/// assert_eq!( "w2=10|w1=01", format_index::<2>(9, 2) );
+/// assert_eq!( "", format_index::<2>(0, 0) );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn format_index<const D: usize>(h: usize, level: usize) -> String {
+    let mut s = String::new();
+    for i in (0..level).rev() {
+        let w = digit::<D>(h, i);
+        if i+1 < level { s.push('|'); }
+        // String への書き込みは失敗しない
+        write!(s, "w{}={:0width$b}", i+1, w, width = D).unwrap();
+    }
+    s
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{coarsen, format_index, from_digits, index_digits, reverse_digits, subcube_at_depth, ToHilbertIndex};
+
+    #[test]
+    fn reverse() {
+        for level in 0..4 {
+            for h in crate::indices::<3>(level) {
+                let r = reverse_digits::<3>(h, level);
+                let mut digits = index_digits::<3>(h, level);
+                digits.reverse();
+                assert_eq!(index_digits::<3>(r, level), digits);
+                assert_eq!(reverse_digits::<3>(r, level), h);
+            }
+        }
+    }
+
+    #[test]
+    fn subcube() {
+        let level = 3;
+        for p in crate::points::<3>(level) {
+            let h = p.to_hilbert_index(level);
+            // depth = 0 の語は符号化で最初に得られる語, すなわち level 1 での部分格子の添字
+            let top = [p[0] >> 2, p[1] >> 2, p[2] >> 2].to_hilbert_index(1);
+            assert_eq!(subcube_at_depth::<3>(h, level, 0), top);
+            let digits = index_digits::<3>(h, level);
+            for (depth, &w) in digits.iter().enumerate() {
+                assert_eq!(subcube_at_depth::<3>(h, level, depth), w);
+                assert_eq!(coarsen::<3>(h, level, depth + 1) & 0b111, w);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "must be less than the level")]
+    fn subcube_too_deep() {
+        subcube_at_depth::<2>(0, 2, 2);
+    }
+
+    #[test]
+    fn digits_inverse() {
+        for level in 0..4 {
+            for h in crate::indices::<3>(level) {
+                let digits = index_digits::<3>(h, level);
+                assert_eq!(digits.len(), level);
+                assert!(digits.iter().all(|&w| w < 8));
+                assert_eq!(from_digits::<3>(&digits), h);
+            }
+        }
+        assert_eq!(index_digits::<2>(usize::MAX, 2), vec![3, 3]);
+    }
+
+    #[test]
+    fn grouping() {
+        assert_eq!(format_index::<3>(0b101_011, 2), "w2=101|w1=011");
+        assert_eq!(format_index::<3>(0b000_001, 2), "w2=000|w1=001");
+        // level を超えるビットは無視する
+        assert_eq!(format_index::<3>(0b111_101_011, 2), "w2=101|w1=011");
+        assert_eq!(format_index::<1>(0b10, 2), "w2=1|w1=0");
+    }
+
+    #[test]
+    fn words_of_encoder() {
+        // 最上位の語は level 1 の曲線上での部分格子の順番に一致する
+        let level = 2;
+        let h = [3, 1, 2].to_hilbert_index(level);
+        let top = [1, 0, 1].to_hilbert_index(1);
+        assert!(format_index::<3>(h, level).starts_with(&format!("w2={:03b}|", top)));
+    }
+}
@@ -0,0 +1,318 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use crate::{emap, gray};
+
+// 基本格子における部分格子の数 2^d - 1
+#[inline]
+fn max(d: usize) -> usize { !( {usize::MAX}<<d ) }
+
+// Gray code の逆変換.
+#[inline]
+fn gc_inv(g: usize, d: usize) -> usize { (1..d).fold(g, |i, j| i^(g>>j)) }
+
+#[inline]
+fn dmap(i: usize, d: usize) -> usize {
+    if i == 0 { 0 } else if i&1 == 0 { gray::changed_bit(i-1) % d } else { gray::changed_bit(i) % d }
+}
+
+// d bit の範囲で右回転
+#[inline]
+fn rotate_right(b: usize, i: usize, d: usize) -> usize {
+    let i = i.rem_euclid(d);
+    if i == 0 { return b & max(d); }
+    ((b >> i) | (b << (d-i))) & max(d)
+}
+
+// d bit の範囲で左回転
+#[inline]
+fn rotate_left(b: usize, i: usize, d: usize) -> usize {
+    let i = i.rem_euclid(d);
+    if i == 0 { return b & max(d); }
+    ((b << i) | (b >> (d-i))) & max(d)
+}
+
+#[inline]
+fn t(b: usize, e: usize, d: usize, dim: usize) -> usize { rotate_right(b^e, d+1, dim) }
+
+#[inline]
+fn t_inv(b: usize, e: usize, d: usize, dim: usize) -> usize { rotate_left(b, d+1, dim)^e }
+
+#[inline]
+fn reduce(p: &[usize], i: usize) -> usize {
+    p.iter().enumerate()
+        .fold(0, |l, (k, p)| l^( ((p >> i)&1) << k))
+}
+
+// level i で有効な次元 (m_k > i となる k) のビットが立ったマスク
+#[cfg(all(feature = "alloc", any(feature = "ndarray", test)))]
+#[inline]
+fn active(levels: &[usize], i: usize) -> usize {
+    levels.iter().enumerate()
+        .fold(0, |mu, (k, &m)| mu|( ((m > i) as usize) << k ))
+}
+
+// Gray code rank の逆変換. 固定されたビット pi を補って (w, gc(w)) を返す.
+#[cfg(all(feature = "alloc", any(feature = "ndarray", test)))]
+#[inline]
+fn gcr_inv(r: usize, mu: usize, pi: usize, dim: usize) -> (usize, usize) {
+    let (mut w, mut l) = (0, 0);
+    let mut j = mu.count_ones() as usize;
+    for k in (0..dim).rev() {
+        let upper = (w >> (k+1))&1;
+        if (mu >> k)&1 == 1 {
+            j -= 1;
+            let bit = (r >> j)&1;
+            w |= bit << k;
+            l |= (bit^upper) << k;
+        } else {
+            let bit = (pi >> k)&1;
+            l |= bit << k;
+            w |= (bit^upper) << k;
+        }
+    }
+    (w, l)
+}
+
+// compact Hilbert index を格子点に変換する. 次元は levels.len() で, k 番目の成分は levels[k] bit.
+// FromHilbertIndex::from_compact_hilbert_index の次元を実行時に与える版.
+#[cfg(all(feature = "alloc", any(feature = "ndarray", test)))]
+pub(crate) fn from_compact_index_into(h: usize, levels: &[usize], out: &mut [usize]) {
+    debug_assert_eq!(levels.len(), out.len());
+    let dim = levels.len();
+    let (mut e, mut d) = (0, 0);
+    let mut k: usize = levels.iter().sum();
+    out.iter_mut().for_each(|x| *x = 0);
+
+    for i in (0..levels.iter().copied().max().unwrap_or(0)).rev() {
+        let mu = rotate_right(active(levels, i), d+1, dim);
+        let pi = rotate_right(e, d+1, dim) & !mu;
+        let b = mu.count_ones() as usize;
+        k -= b;
+        let r = (h >> k) & !( {usize::MAX}<<b );
+        let (w, l) = gcr_inv(r, mu, pi, dim);
+        let l = t_inv(l, e, d, dim);
+        for (j, x) in out.iter_mut().enumerate() {
+            *x = (*x << 1)|((l >> j)&1);
+        }
+        e ^= rotate_left(emap(w), d+1, dim);
+        d = ( d + dmap(w, dim) + 1 )%dim;
+    }
+}
+
+/// D-dimensional Hilbert curve whose dimension `d` is given at runtime.
+/// 
+/// This mirrors `ToHilbertIndex` and `FromHilbertIndex`,
+/// but takes slices and returns `Vec` instead of `[usize; D]`.
+/// The results are identical to the const-generic traits with `D == d`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::DynHilbert;
+/// 
+/// let curve = DynHilbert::new(2, 3);
+/// 
+/// assert_eq!( 2, curve.to_index(&[1, 1]) );
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!( vec![1, 1], curve.from_index(2) );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynHilbert {
+    d: usize,
+    level: usize,
+}
+
+impl DynHilbert {
+    /// Create a `d`-dimensional Hilbert curve with a given level.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `d == 0` or `d >= usize::BITS`.
+    pub fn new(d: usize, level: usize) -> Self {
+        assert!(d >= 1, "DynHilbert: the dimension must be at least 1");
+        assert!(d < usize::BITS as usize, "DynHilbert: the dimension must be less than usize::BITS");
+        DynHilbert { d, level }
+    }
+
+    /// The dimension of grid points.
+    pub fn d(&self) -> usize { self.d }
+
+    /// The level (order) of the curve.
+    pub fn level(&self) -> usize { self.level }
+
+    /// Convert a grid point to a Hilbert index.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `point.len() != self.d`.
+    pub fn to_index(&self, point: &[usize]) -> usize {
+        assert_eq!(point.len(), self.d,
+            "DynHilbert: expected a point of dimension {}, but got {}", self.d, point.len());
+
+        let dim = self.d;
+        let (mut h, mut e, mut d) = (0, 0, 0);
+        for i in (0..self.level).rev() {
+            let l = t(reduce(point, i), e, d, dim);
+            let w = gc_inv(l, dim);
+            e ^= rotate_left(emap(w), d+1, dim);
+            d = ( d + dmap(w, dim) + 1 )%dim;
+            h = (h << dim) | w;
+        }
+
+        h
+    }
+
+    /// Convert a Hilbert index to a grid point of length `self.d` (requires the `alloc` feature).
+    #[cfg(feature = "alloc")]
+    pub fn from_index(&self, h: usize) -> Vec<usize> {
+        let mut p = vec![0; self.d];
+        self.from_index_into(h, &mut p);
+        p
+    }
+
+    /// Convert a Hilbert index to a grid point, written into `out`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `out.len() != self.d`.
+    pub fn from_index_into(&self, h: usize, out: &mut [usize]) {
+        assert_eq!(out.len(), self.d,
+            "DynHilbert: expected a buffer of length {}, but got {}", self.d, out.len());
+
+        let dim = self.d;
+        let (mut e, mut d) = (0, 0);
+        out.iter_mut().for_each(|x| *x = 0);
+
+        for i in (0..self.level).rev() {
+            let w = (h >> (i*dim)) & max(dim);
+            let l = t_inv(gray::encode(w), e, d, dim);
+            for (j, x) in out.iter_mut().enumerate() {
+                *x = (*x << 1)|((l >> j)&1);
+            }
+            e ^= rotate_left(emap(w), d+1, dim);
+            d = ( d + dmap(w, dim) + 1 )%dim;
+        }
+    }
+}
+
+/// Convert a Hilbert index to a `d`-dimensional grid point, where `d` is given at runtime (requires the `alloc` feature).
+/// 
+/// This is a shorthand of `DynHilbert::new(d, level).from_index(h)`,
+/// for callers such as plugins and FFI where the dimension is not a type parameter.
+/// The result is identical to `FromHilbertIndex::<D>::from_hilbert_index` with `D == d`.
+/// 
+/// # Panics
+/// 
+/// Panics if `d == 0` or `d >= usize::BITS`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_vec, FromHilbertIndex};
+/// 
+/// let level = 2;
+/// let p: [usize; 3] = 27.from_hilbert_index(level);
+/// assert_eq!( p.to_vec(), from_hilbert_index_vec(27, 3, level) );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn from_hilbert_index_vec(h: usize, d: usize, level: usize) -> Vec<usize> {
+    DynHilbert::new(d, level).from_index(h)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use crate::{from_hilbert_index_vec, indices, DynHilbert, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        let curve = DynHilbert::new(D, level);
+        for key in 0..2usize.pow((D*level) as u32) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(curve.from_index(key), xyz.to_vec());
+            assert_eq!(curve.to_index(&xyz), xyz.to_hilbert_index(level));
+
+            let mut out = [1; D];
+            curve.from_index_into(key, &mut out);
+            assert_eq!(out, xyz);
+        }
+    }
+
+    #[test]
+    fn same_as_const_generic() {
+        for level in 0..5 {
+            check::<2>(level);
+            check::<3>(level);
+            check::<4>(level);
+        }
+        for level in 0..3 {
+            check::<5>(level);
+            check::<6>(level);
+        }
+    }
+
+    fn check_compact<const D: usize>(levels: [usize; D]) {
+        let mut out = [1; D];
+        for key in 0..2usize.pow(levels.iter().sum::<usize>() as u32) {
+            super::from_compact_index_into(key, &levels, &mut out);
+            assert_eq!(out, key.from_compact_hilbert_index(levels));
+        }
+    }
+
+    #[test]
+    fn compact_same_as_const_generic() {
+        check_compact([3, 1]);
+        check_compact([0, 4]);
+        check_compact([3, 3]);
+        check_compact([3, 1, 2]);
+        check_compact([1, 3, 2, 2]);
+        check_compact([4, 0, 1, 2, 2]);
+    }
+
+    #[test]
+    fn full_rotation() {
+        for i in [0, 1, 2, usize::MAX] {
+            assert_eq!(super::rotate_right(1, i, 1), 1);
+            assert_eq!(super::rotate_left(1, i, 1), 1);
+        }
+        let w = usize::BITS as usize - 1;
+        assert_eq!(super::rotate_right(0b101, w, w), 0b101);
+        assert_eq!(super::rotate_right(1, 1, w), 1 << (w-1));
+        assert_eq!(super::rotate_left(1 << (w-1), 1, w), 1);
+    }
+
+    #[test]
+    fn vec_same_as_const_generic() {
+        for level in 0..4 {
+            for h in indices::<3>(level) {
+                let p: [usize; 3] = h.from_hilbert_index(level);
+                assert_eq!(from_hilbert_index_vec(h, 3, level), p.to_vec());
+            }
+        }
+        assert_eq!(from_hilbert_index_vec(5, 1, 4), vec![5]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the dimension must be at least 1")]
+    fn zero_dimension() {
+        DynHilbert::new(0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "the dimension must be less than usize::BITS")]
+    fn too_large_dimension() {
+        DynHilbert::new(usize::BITS as usize, 0);
+    }
+
+    #[test]
+    fn getters() {
+        let curve = DynHilbert::new(3, 5);
+        assert_eq!((curve.d(), curve.level()), (3, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a point of dimension 3")]
+    fn wrong_dimension() {
+        DynHilbert::new(3, 2).to_index(&[0, 1]);
+    }
+}
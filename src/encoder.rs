@@ -0,0 +1,160 @@
+use crate::ToHilbertIndex;
+
+/// An encoder which receives the components of a grid point one axis at a time.
+/// 
+/// The components are buffered in `[usize; D]` and converted by `to_hilbert_index` in `finish`.
+/// They can be pushed in any order, but every axis must be set exactly once.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{HilbertEncoder, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let mut encoder = HilbertEncoder::<3>::new();
+/// encoder.push_axis(2, 7);
+/// encoder.push_axis(0, 1);
+/// encoder.push_axis(1, 4);
+/// assert_eq!( [1, 4, 7].to_hilbert_index(level), encoder.finish(level) );
+/// ```
+/// 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HilbertEncoder<const D: usize> {
+    point: [usize; D],
+    set: [bool; D],
+}
+
+impl<const D: usize> HilbertEncoder<D> {
+    /// Create an encoder with no axis set.
+    pub fn new() -> Self {
+        HilbertEncoder { point: [0; D], set: [false; D] }
+    }
+
+    /// Set the component of the grid point along `axis` to `value`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `axis >= D` or `axis` is already set.
+    pub fn push_axis(&mut self, axis: usize, value: usize) {
+        assert!(axis < D, "HilbertEncoder: axis {} is out of range 0..{}", axis, D);
+        assert!(!self.set[axis], "HilbertEncoder: axis {} is set twice", axis);
+        self.point[axis] = value;
+        self.set[axis] = true;
+    }
+
+    /// Whether all the axes have been set.
+    pub fn is_complete(&self) -> bool {
+        self.set.iter().all(|&s| s)
+    }
+
+    /// Convert the buffered grid point to a Hilbert index, same as `to_hilbert_index(level)`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if some axis has not been set.
+    pub fn finish(self, level: usize) -> usize {
+        if let Some(axis) = self.set.iter().position(|&s| !s) {
+            panic!("HilbertEncoder: axis {} is not set", axis);
+        }
+        self.point.to_hilbert_index(level)
+    }
+}
+
+impl<const D: usize> Default for HilbertEncoder<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert coordinates given by an iterator to a Hilbert index.
+/// 
+/// Exactly `D` coordinates are collected into `[usize; D]` on the stack,
+/// which is then converted by `to_hilbert_index`.
+/// This is useful in the middle of an iterator chain, without building the array by hand.
+/// 
+/// # Panics
+/// 
+/// Panics if the iterator yields fewer or more than `D` coordinates.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{to_hilbert_index_from_iter, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// let p = vec![1, 2, 3];
+/// assert_eq!( [2, 3, 4].to_hilbert_index(level), to_hilbert_index_from_iter::<3>(p.iter().map(|x| x + 1), level) );
+/// ```
+/// 
+pub fn to_hilbert_index_from_iter<const D: usize>(coords: impl IntoIterator<Item = usize>, level: usize) -> usize {
+    let mut point = [0; D];
+    let mut coords = coords.into_iter();
+    for (k, x) in point.iter_mut().enumerate() {
+        *x = coords.next()
+            .unwrap_or_else(|| panic!("to_hilbert_index_from_iter: expected {} coordinates, but got {}", D, k));
+    }
+    assert!(coords.next().is_none(), "to_hilbert_index_from_iter: expected {} coordinates, but got more", D);
+
+    point.to_hilbert_index(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, to_hilbert_index_from_iter, FromHilbertIndex, HilbertEncoder, ToHilbertIndex};
+
+    #[test]
+    fn any_order() {
+        const D: usize = 3;
+        let level = 3;
+        let orders = [[0, 1, 2], [2, 1, 0], [1, 2, 0], [2, 0, 1]];
+        for h in indices::<D>(level) {
+            let p: [usize; D] = h.from_hilbert_index(level);
+            for order in orders.iter() {
+                let mut encoder = HilbertEncoder::<D>::new();
+                for &axis in order.iter() {
+                    assert!(!encoder.is_complete());
+                    encoder.push_axis(axis, p[axis]);
+                }
+                assert!(encoder.is_complete());
+                assert_eq!(encoder.finish(level), p.to_hilbert_index(level));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "axis 1 is set twice")]
+    fn set_twice() {
+        let mut encoder = HilbertEncoder::<2>::new();
+        encoder.push_axis(1, 0);
+        encoder.push_axis(1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "axis 2 is not set")]
+    fn not_set() {
+        let mut encoder = HilbertEncoder::<3>::default();
+        encoder.push_axis(0, 0);
+        encoder.push_axis(1, 1);
+        encoder.finish(2);
+    }
+
+    #[test]
+    fn from_iter() {
+        let level = 3;
+        assert_eq!(to_hilbert_index_from_iter::<3>([1, 2, 3].iter().copied(), level), [1, 2, 3].to_hilbert_index(level));
+        assert_eq!(to_hilbert_index_from_iter::<3>(vec![1, 2, 3], level), [1, 2, 3].to_hilbert_index(level));
+        assert_eq!(to_hilbert_index_from_iter::<2>((0..2).rev(), level), [1, 0].to_hilbert_index(level));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3 coordinates, but got 2")]
+    fn from_iter_short() {
+        to_hilbert_index_from_iter::<3>([1, 2].iter().copied(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 2 coordinates, but got more")]
+    fn from_iter_long() {
+        to_hilbert_index_from_iter::<2>(0..3, 3);
+    }
+}
@@ -0,0 +1,36 @@
+use core::fmt;
+
+/// An error returned by the checked conversions such as `try_to_hilbert_index`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HilbertError {
+    /// The `dim`-th component `value` of a grid point does not satisfy `value < bound`.
+    OutOfRange { dim: usize, value: usize, bound: usize },
+    /// A Hilbert index `index` does not satisfy `index < bound`.
+    IndexOutOfRange { index: usize, bound: usize },
+    /// The `dim`-th component `value` of a grid point centered at the origin does not satisfy `min <= value < max`.
+    SignedOutOfRange { dim: usize, value: isize, min: isize, max: isize },
+    /// The `level` exceeds `max_level`, so that the Hilbert indices do not fit in `usize`.
+    LevelOutOfRange { level: usize, max_level: usize },
+}
+
+impl fmt::Display for HilbertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HilbertError::OutOfRange { dim, value, bound } => {
+                write!(f, "component {} of the grid point is {}, which is out of range 0..{}", dim, value, bound)
+            },
+            HilbertError::IndexOutOfRange { index, bound } => {
+                write!(f, "Hilbert index {} is out of range 0..{}", index, bound)
+            },
+            HilbertError::SignedOutOfRange { dim, value, min, max } => {
+                write!(f, "component {} of the grid point is {}, which is out of range {}..{}", dim, value, min, max)
+            },
+            HilbertError::LevelOutOfRange { level, max_level } => {
+                write!(f, "level {} exceeds the maximum level {}, for which Hilbert indices fit in usize", level, max_level)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HilbertError {}
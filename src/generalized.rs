@@ -0,0 +1,196 @@
+use core::convert::TryFrom;
+use crate::{max_level, FromHilbertIndex, ToHilbertIndex};
+
+/// A `D`-dimensional space-filling curve whose cells are refined by `radix` per axis at each level.
+/// 
+/// The grid points satisfy `x < radix.pow(level)` for each component, and the indices lie in
+/// `0..radix.pow(D*level)`, whose base-`radix` digits play the role of the bits of the Hilbert index.
+/// Adjacent indices give adjacent grid points, and the curve starts at the origin.
+/// The supported radices are:
+/// 
+/// * powers of two `2.pow(k)`: the Hilbert curve, where a base-`radix` digit consists of `k` bits.
+///   Hence `radix = 2` gives `to_hilbert_index(level)` and `radix = 4` gives `to_hilbert_index(2*level)`.
+/// * odd numbers: the Peano curve, built on the base-`radix` reflected Gray code.
+///   Each digit of the `k`-th component is reflected (`x -> radix - 1 - x`)
+///   if the sum of the preceding digits of the index for the other components is odd.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::GeneralizedHilbert;
+/// 
+/// let curve = GeneralizedHilbert::<2>::new(3, 1);
+/// let order: Vec<_> = (0..9).map(|h| curve.from_index(h)).collect();
+/// assert_eq!( vec![[0, 0], [0, 1], [0, 2], [1, 2], [1, 1], [1, 0], [2, 0], [2, 1], [2, 2]], order );
+/// assert_eq!( 4, curve.to_index(&[1, 1]) );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GeneralizedHilbert<const D: usize> {
+    radix: usize,
+    level: usize,
+}
+
+impl<const D: usize> GeneralizedHilbert<D> {
+    /// Create a curve with a given radix and level.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `radix` is neither a power of two nor an odd number larger than `1`,
+    /// or if `radix.pow(D*level)` overflows `usize`.
+    pub fn new(radix: usize, level: usize) -> Self {
+        assert!(radix >= 2 && (radix.is_power_of_two() || radix%2 == 1),
+            "GeneralizedHilbert: radix {} is neither a power of two nor an odd number", radix);
+        let fits = D.checked_mul(level)
+            .and_then(|n| u32::try_from(n).ok())
+            .and_then(|n| radix.checked_pow(n))
+            .is_some();
+        assert!(fits, "GeneralizedHilbert: {}.pow({}*{}) overflows usize", radix, D, level);
+        GeneralizedHilbert { radix, level }
+    }
+
+    /// The radix of the curve.
+    pub fn radix(&self) -> usize { self.radix }
+
+    /// The level of the curve.
+    pub fn level(&self) -> usize { self.level }
+
+    /// The number of grid points along each axis, i.e. `radix.pow(level)`.
+    pub fn side(&self) -> usize { self.radix.pow(self.level as u32) }
+
+    /// The number of grid points, i.e. `radix.pow(D*level)`.
+    pub fn len(&self) -> usize { self.radix.pow((D*self.level) as u32) }
+
+    /// Always `false`, since the curve has at least one grid point.
+    pub fn is_empty(&self) -> bool { false }
+
+    // 2^bits の基数を 1 桁 bits bit の Hilbert curve とみなしたときの level
+    #[inline]
+    fn binary_level(&self) -> Option<usize> {
+        if self.radix.is_power_of_two() {
+            let level = self.radix.trailing_zeros() as usize * self.level;
+            debug_assert!(level <= max_level::<D>());
+            Some(level)
+        } else {
+            None
+        }
+    }
+
+    /// Convert a grid point to an index.
+    pub fn to_index(&self, p: &[usize; D]) -> usize {
+        if let Some(level) = self.binary_level() {
+            return p.to_hilbert_index(level);
+        }
+
+        // Peano curve: 各成分の桁を上位から交互に並べ, 他の成分の桁の和が奇数なら反転する
+        let b = self.radix;
+        let scale = self.side() / b;
+        let (mut h, mut sum, mut own) = (0, 0, [0; D]);
+        for i in 0..self.level {
+            let unit = scale / b.pow(i as u32);
+            for k in 0..D {
+                let x = (p[k] / unit)%b;
+                let t = if (sum - own[k])%2 == 1 { b - 1 - x } else { x };
+                h = h*b + t;
+                sum += t;
+                own[k] += t;
+            }
+        }
+
+        h
+    }
+
+    /// Convert an index to a grid point. This is the inverse of `to_index`.
+    pub fn from_index(&self, h: usize) -> [usize; D] {
+        if let Some(level) = self.binary_level() {
+            return h.from_hilbert_index(level);
+        }
+
+        let b = self.radix;
+        let mut unit = self.len();
+        let (mut p, mut sum, mut own) = ([0; D], 0, [0; D]);
+        for _ in 0..self.level {
+            for k in 0..D {
+                unit /= b;
+                let t = (h / unit)%b;
+                let x = if (sum - own[k])%2 == 1 { b - 1 - t } else { t };
+                p[k] = p[k]*b + x;
+                sum += t;
+                own[k] += t;
+            }
+        }
+
+        p
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromHilbertIndex, GeneralizedHilbert};
+
+    fn check<const D: usize>(radix: usize, level: usize) {
+        let curve = GeneralizedHilbert::<D>::new(radix, level);
+        let side = curve.side();
+        let mut seen = vec![false; curve.len()];
+        let mut prv: Option<[usize; D]> = None;
+        for h in 0..curve.len() {
+            let p = curve.from_index(h);
+            assert!(p.iter().all(|&x| x < side));
+            assert_eq!(curve.to_index(&p), h);
+
+            // 全単射
+            let k = p.iter().fold(0, |k, &x| k*side + x);
+            assert!(!seen[k]);
+            seen[k] = true;
+
+            if let Some(prv) = prv {
+                let diff = prv.iter().zip(p.iter())
+                    .map(|(&a, &b)| (a as isize - b as isize).abs())
+                    .sum::<isize>();
+                assert_eq!(diff, 1);
+            }
+            prv = Some(p);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for level in 0..5 {
+            check::<2>(3, level);
+        }
+        for level in 0..4 {
+            check::<2>(2, level);
+            check::<2>(4, level);
+            check::<3>(3, level);
+        }
+        check::<2>(5, 2);
+        check::<2>(7, 2);
+        check::<3>(5, 2);
+        check::<4>(3, 2);
+        check::<3>(8, 2);
+    }
+
+    #[test]
+    fn power_of_two() {
+        let level = 3;
+        let base2 = GeneralizedHilbert::<3>::new(2, 2*level);
+        let base4 = GeneralizedHilbert::<3>::new(4, level);
+        for h in 0..base4.len() {
+            let p: [usize; 3] = h.from_hilbert_index(2*level);
+            assert_eq!(base2.from_index(h), p);
+            assert_eq!(base4.from_index(h), p);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is neither a power of two nor an odd number")]
+    fn unsupported_radix() {
+        GeneralizedHilbert::<2>::new(6, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn overflow() {
+        GeneralizedHilbert::<3>::new(3, 20);
+    }
+}
@@ -0,0 +1,91 @@
+use geo::{Coord, Rect};
+use crate::{from_unit_coords, to_unit_coords};
+
+/// Convert a geographic coordinate to a 2D Hilbert index within a bounding box (requires the `geo` feature).
+/// 
+/// The position of `coord` in `bbox` is scaled to the grid `[0, 2.pow(level))` per axis,
+/// with `x` (e.g. longitude) as the first component and `y` (e.g. latitude) as the second one,
+/// and converted to a Hilbert index as in `from_unit_coords`.
+/// Coordinates outside the box are clamped to its edge. Both `geo::Coord` and `geo::Point` are accepted.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use geo::{coord, point, Rect};
+/// use hilbert_index::{index_coord, ToHilbertIndex};
+/// 
+/// let bbox = Rect::new(coord! { x: -180.0, y: -90.0 }, coord! { x: 180.0, y: 90.0 });
+/// let level = 8;
+/// let tokyo = index_coord(point! { x: 139.69, y: 35.69 }, bbox, level);
+/// assert_eq!( [227, 178].to_hilbert_index(level), tokyo );
+/// ```
+/// 
+pub fn index_coord<C: Into<Coord<f64>>>(coord: C, bbox: Rect<f64>, level: usize) -> usize {
+    let c = coord.into();
+    let (min, max) = (bbox.min(), bbox.max());
+    from_unit_coords([(c.x - min.x)/(max.x - min.x), (c.y - min.y)/(max.y - min.y)], level)
+}
+
+/// Convert a 2D Hilbert index to the center of its cell within a bounding box (requires the `geo` feature).
+/// 
+/// This is the inverse of `index_coord`, up to the size of a cell.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use geo::{coord, Rect};
+/// use hilbert_index::{coord_from_index, index_coord};
+/// 
+/// let bbox = Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 4.0, y: 4.0 });
+/// let level = 2;
+/// assert_eq!( coord! { x: 1.5, y: 2.5 }, coord_from_index(index_coord(coord! { x: 1.2, y: 2.9 }, bbox, level), bbox, level) );
+/// ```
+/// 
+pub fn coord_from_index(h: usize, bbox: Rect<f64>, level: usize) -> Coord<f64> {
+    let [u, v] = to_unit_coords::<2>(h, level);
+    let (min, max) = (bbox.min(), bbox.max());
+    Coord { x: min.x + u*(max.x - min.x), y: min.y + v*(max.y - min.y) }
+}
+
+#[cfg(test)]
+mod tests {
+    use geo::{coord, point, Rect};
+    use crate::{coord_from_index, curve_distance, index_coord, FromHilbertIndex, ToHilbertIndex};
+
+    fn world() -> Rect<f64> {
+        Rect::new(coord! { x: -180.0, y: -90.0 }, coord! { x: 180.0, y: 90.0 })
+    }
+
+    #[test]
+    fn nearby_points() {
+        let level = 16;
+        // 東京駅と有楽町駅 (約 600 m)
+        let a = index_coord(coord! { x: 139.7671, y: 35.6812 }, world(), level);
+        let b = index_coord(coord! { x: 139.7630, y: 35.6751 }, world(), level);
+        let pa: [usize; 2] = a.from_hilbert_index(level);
+        let pb: [usize; 2] = b.from_hilbert_index(level);
+        assert!(curve_distance(&pa, &pb, level) < 256, "{} and {}", a, b);
+        // 地球の反対側は遠い
+        let c = index_coord(coord! { x: -40.0, y: -35.0 }, world(), level);
+        assert!(a.abs_diff(c) > 1 << 24);
+    }
+
+    #[test]
+    fn cell_center() {
+        let level = 10;
+        for &(x, y) in [(139.7671, 35.6812), (-0.1276, 51.5072), (-180.0, -90.0), (179.9, 89.9)].iter() {
+            let h = index_coord(point! { x: x, y: y }, world(), level);
+            let c = coord_from_index(h, world(), level);
+            assert!((c.x - x).abs() <= 180.0 / 1024.0);
+            assert!((c.y - y).abs() <= 90.0 / 1024.0);
+            assert_eq!(index_coord(c, world(), level), h);
+        }
+    }
+
+    #[test]
+    fn clamp_outside() {
+        let level = 4;
+        assert_eq!(index_coord(coord! { x: 200.0, y: -100.0 }, world(), level), [15, 0].to_hilbert_index(level));
+        assert_eq!(index_coord(coord! { x: 180.0, y: 90.0 }, world(), level), [15, 15].to_hilbert_index(level));
+    }
+}
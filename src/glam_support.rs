@@ -0,0 +1,104 @@
+use glam::{UVec2, UVec3};
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+// glam の u32 成分を usize に広げれば, 残りは trait の既定の実装が与える
+macro_rules! impl_to_hilbert_index {
+    ($vec:ty, $d:expr) => {
+        impl ToHilbertIndex<$d> for $vec {
+            #[inline]
+            fn to_grid_point(&self) -> [usize; $d] {
+                to_array(self.to_array())
+            }
+        }
+    };
+}
+
+impl_to_hilbert_index!(UVec2, 2);
+impl_to_hilbert_index!(UVec3, 3);
+
+#[inline]
+fn to_array<const D: usize>(v: [u32; D]) -> [usize; D] {
+    let mut p = [0; D];
+    for (x, &y) in p.iter_mut().zip(v.iter()) {
+        *x = y as usize;
+    }
+    p
+}
+
+/// Convert a Hilbert index `usize` to a grid point `glam::UVec2` (requires the `glam` feature).
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index` for `UVec2`.
+/// Since the components are `u32`, the level is at most `max_level::<2>()`, which is `31` on 64-bit platforms;
+/// then every component `x` satisfies `x < 2.pow(level)` and fits in `u32`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use glam::UVec2;
+/// use hilbert_index::{from_hilbert_index_uvec2, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let h = UVec2::new(3, 5).to_hilbert_index(level);
+/// assert_eq!( [3, 5].to_hilbert_index(level), h );
+/// assert_eq!( UVec2::new(3, 5), from_hilbert_index_uvec2(h, level) );
+/// ```
+/// 
+pub fn from_hilbert_index_uvec2(h: usize, level: usize) -> UVec2 {
+    let [x, y]: [usize; 2] = h.from_hilbert_index(level);
+    UVec2::new(x as u32, y as u32)
+}
+
+/// Convert a Hilbert index `usize` to a grid point `glam::UVec3` (requires the `glam` feature).
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index` for `UVec3`.
+/// The level is at most `max_level::<3>()`, so every component fits in `u32`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use glam::UVec3;
+/// use hilbert_index::{from_hilbert_index_uvec3, ToHilbertIndex};
+/// 
+/// let level = 4;
+/// let h = UVec3::new(3, 5, 9).to_hilbert_index(level);
+/// assert_eq!( UVec3::new(3, 5, 9), from_hilbert_index_uvec3(h, level) );
+/// ```
+/// 
+pub fn from_hilbert_index_uvec3(h: usize, level: usize) -> UVec3 {
+    let [x, y, z]: [usize; 3] = h.from_hilbert_index(level);
+    UVec3::new(x as u32, y as u32, z as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{UVec2, UVec3};
+    use crate::{from_hilbert_index_uvec2, from_hilbert_index_uvec3, indices, FromHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn same_as_array() {
+        let level = 4;
+        for h in indices::<2>(level) {
+            let v = from_hilbert_index_uvec2(h, level);
+            let p: [usize; 2] = h.from_hilbert_index(level);
+            assert_eq!([v.x as usize, v.y as usize], p);
+            assert_eq!(v.to_hilbert_index(level), h);
+            assert_eq!(v.to_compact_hilbert_index([level; 2]), h);
+        }
+        for h in indices::<3>(level) {
+            let v = from_hilbert_index_uvec3(h, level);
+            let p: [usize; 3] = h.from_hilbert_index(level);
+            assert_eq!([v.x as usize, v.y as usize, v.z as usize], p);
+            assert_eq!(v.to_hilbert_index(level), h);
+            assert_eq!(v.try_to_hilbert_index(level), Ok(h));
+        }
+    }
+
+    #[test]
+    fn max_level_fits_u32() {
+        let level = crate::max_level::<2>();
+        let v = UVec2::new(u32::MAX >> (32 - level), 7);
+        assert_eq!(from_hilbert_index_uvec2(v.to_hilbert_index(level), level), v);
+        let v = UVec3::new(1, 2, 3);
+        assert!(v.try_to_hilbert_index(1).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Gray code and bit rotation primitives used by the Hilbert curve.
+//! 
+//! Functions with a const parameter `D` act on `D`-bit words,
+//! i.e. the results are masked by `2.pow(D) - 1`.
+//! The inputs are assumed to be `D`-bit words as well.
+
+use crate::max;
+
+/// The binary reflected Gray code of `i`.
+/// 
+/// ```
+/// use hilbert_index::gray;
+/// 
+/// assert_eq!( 6, gray::encode(4) );
+/// ```
+/// 
+#[inline]
+pub const fn encode(i: usize) -> usize { i^(i >> 1) }
+
+/// The inverse of `encode` for `D`-bit words.
+/// 
+/// ```
+/// use hilbert_index::gray;
+/// 
+/// for i in 0..8 {
+///     assert_eq!( i, gray::decode::<3>(gray::encode(i)) );
+/// }
+/// ```
+/// 
+#[inline]
+pub const fn decode<const D: usize>(g: usize) -> usize {
+    let (mut i, mut j) = (g, 1);
+    while j < D {
+        i ^= g >> j;
+        j += 1;
+    }
+    i
+}
+
+/// The position of the bit which changes between `encode(i)` and `encode(i + 1)`,
+/// i.e. the number of trailing ones of `i`.
+/// 
+/// ```
+/// use hilbert_index::gray;
+/// 
+/// assert_eq!( 2, gray::changed_bit(3) );
+/// assert_eq!( gray::encode(3)^gray::encode(4), 1 << gray::changed_bit(3) );
+/// ```
+/// 
+#[inline]
+pub const fn changed_bit(i: usize) -> usize {
+    (!i).trailing_zeros() as usize
+}
+
+/// Rotate a `D`-bit word `b` to the right by `i` bits (modulo `D`).
+/// 
+/// The bits shifted out of the lowest bit come back from the `(D-1)`-th bit,
+/// and the bits above `D` are masked out by `2.pow(D) - 1`.
+/// Rotations by multiples of `D`, including every rotation for `D = 1`, return `b` as it is,
+/// and no shift by `D` or more bits is performed.
+/// For `D = 32` on 64-bit platforms this is `u32::rotate_right`.
+/// 
+/// ```
+/// use hilbert_index::gray;
+/// 
+/// assert_eq!( 0b100, gray::rotate_right::<3>(0b001, 1) );
+/// assert_eq!( 0b011, gray::rotate_right::<3>(0b110, 1) );
+/// ```
+/// 
+#[inline]
+pub const fn rotate_right<const D: usize>(b: usize, i: usize) -> usize {
+    // 結果全体をマスクする. 以前の `(b >> i)^(b << (D-i))&max` はマスクが左シフトの項にしか掛からないが,
+    // 入力が D bit なら `b >> i` も D bit に収まるため, 余分な上位ビットは残らなかった.
+    // i を D で割った余りに直し, 0 (D = 1 の場合は常に 0) は別扱いにすることで,
+    // シフト量は常に 1..D-1 の範囲に収まる
+    let i = i.rem_euclid(D);
+    if i == 0 { return b & max::<D>(); }
+    ((b >> i) | (b << (D-i))) & max::<D>()
+}
+
+/// Rotate a `D`-bit word `b` to the left by `i` bits (modulo `D`).
+/// 
+/// This is the inverse of `rotate_right`, and the result is masked by `2.pow(D) - 1` as well.
+/// 
+/// ```
+/// use hilbert_index::gray;
+/// 
+/// assert_eq!( 0b001, gray::rotate_left::<3>(0b100, 1) );
+/// assert_eq!( 0b101, gray::rotate_left::<3>(0b110, 1) );
+/// ```
+/// 
+#[inline]
+pub const fn rotate_left<const D: usize>(b: usize, i: usize) -> usize {
+    let i = i.rem_euclid(D);
+    if i == 0 { return b & max::<D>(); }
+    ((b << i) | (b >> (D-i))) & max::<D>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rotate_left, rotate_right};
+
+    // 1 bit ずつ回す素朴な実装
+    fn naive_right<const D: usize>(b: usize, i: usize) -> usize {
+        (0..i%D).fold(b, |b, _| (b >> 1) | ((b&1) << (D-1)))
+    }
+
+    // 1 bit ずつ組み立てる参照実装: 結果の k bit 目は b の (k+i)%D bit 目
+    fn bitwise_right<const D: usize>(b: usize, i: usize) -> usize {
+        (0..D).fold(0, |r, k| r | (((b >> ((k + i)%D))&1) << k))
+    }
+
+    // 括弧を明示する前の実装
+    #[allow(clippy::precedence)]
+    fn old_right<const D: usize>(b: usize, i: usize) -> usize {
+        let i = i.rem_euclid(D);
+        (b >> i)^(b << (D-i))&crate::max::<D>()
+    }
+
+    fn check<const D: usize>() {
+        for b in 0..1 << D {
+            for i in 0..D {
+                assert_eq!(rotate_right::<D>(b, i), bitwise_right::<D>(b, i), "rotate_right::<{}>({:#b}, {})", D, b, i);
+                assert_eq!(rotate_left::<D>(bitwise_right::<D>(b, i), i), b);
+                assert_eq!(rotate_right::<D>(b, i), old_right::<D>(b, i));
+            }
+            for i in 0..3*D {
+                let r = rotate_right::<D>(b, i);
+                assert!(r < 1 << D);
+                assert_eq!(r, naive_right::<D>(b, i), "rotate_right::<{}>({:#b}, {})", D, b, i);
+                assert_eq!(rotate_left::<D>(r, i), b);
+                assert_eq!(rotate_right::<D>(rotate_left::<D>(b, i), i), b);
+                assert_eq!(rotate_left::<D>(b, i), rotate_right::<D>(b, D - i%D));
+            }
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<5>();
+        check::<6>();
+        check::<7>();
+        check::<8>();
+    }
+
+    // 以前の `(b >> i)^(b << (D-i))&max` は `&` が `^` より先に結合するため,
+    // D bit の入力に対しては正しかった. 括弧を明示して書き直した後も同じ値になること.
+    #[test]
+    fn precedence() {
+        assert_eq!(rotate_right::<3>(0b111, 0), 0b111);
+        assert_eq!(rotate_right::<3>(0b011, 2), 0b110);
+        assert_eq!(rotate_left::<3>(0b110, 2), 0b011);
+        assert_eq!(rotate_left::<4>(0b1001, 3), 0b1100);
+    }
+
+    // D = 1 や回転量が D の倍数の場合, シフト量が語の幅に達しないこと
+    #[test]
+    fn full_rotation() {
+        for b in 0..2 {
+            for i in [0, 1, 2, 7, usize::MAX] {
+                assert_eq!(rotate_right::<1>(b, i), b);
+                assert_eq!(rotate_left::<1>(b, i), b);
+            }
+        }
+        for i in [0, 5, 10, 5*1000] {
+            assert_eq!(rotate_right::<5>(0b10110, i), 0b10110);
+            assert_eq!(rotate_left::<5>(0b10110, i), 0b10110);
+        }
+        const W: usize = usize::BITS as usize - 1;
+        let b = crate::max::<W>() ^ 0b10;
+        assert_eq!(rotate_right::<W>(b, W), b);
+        assert_eq!(rotate_left::<W>(b, 2*W), b);
+        assert_eq!(rotate_right::<W>(1, 1), 1 << (W-1));
+        assert_eq!(rotate_left::<W>(1 << (W-1), 1), 1);
+        assert_eq!(rotate_left::<W>(1, W-1), 1 << (W-1));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn same_as_intrinsic() {
+        for &b in [0u32, 1, 0x8000_0001, 0xdead_beef, u32::MAX].iter() {
+            for i in 0..64 {
+                assert_eq!(rotate_right::<32>(b as usize, i), b.rotate_right(i as u32) as usize);
+                assert_eq!(rotate_left::<32>(b as usize, i), b.rotate_left(i as u32) as usize);
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use core::ops::Range;
+use crate::{max_level, num_cells};
+
+/// The Hilbert index of the cell at `to_level` which contains the cell `h` at `from_level`.
+/// 
+/// A cell at level `l` is divided into `2.pow(D)` cells at level `l+1`,
+/// and the Hilbert curve visits them in a row.
+/// Hence the enclosing coarse cell is obtained by dropping the low `D*(from_level - to_level)` bits,
+/// without converting to a grid point.
+/// 
+/// # Panics
+/// 
+/// Panics if `to_level > from_level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{coarsen, FromHilbertIndex, ToHilbertIndex};
+/// const D: usize = 2;
+/// 
+/// let p = [5, 6];
+/// let h = p.to_hilbert_index(3);
+/// assert_eq!( [2, 3].to_hilbert_index(2), coarsen::<D>(h, 3, 2) );
+/// assert_eq!( [1, 1].to_hilbert_index(1), coarsen::<D>(h, 3, 1) );
+/// ```
+/// 
+pub fn coarsen<const D: usize>(h: usize, from_level: usize, to_level: usize) -> usize {
+    assert!(to_level <= from_level, "coarsen: to_level {} exceeds from_level {}", to_level, from_level);
+    h.checked_shr((D*(from_level - to_level)) as u32).unwrap_or(0)
+}
+
+/// The Hilbert indices at `level+1` of the `2.pow(D)` cells contained in the cell `h` at `level`.
+/// 
+/// They are contiguous, so the result is given as a range `(h << D)..((h + 1) << D)`,
+/// ordered in the Hilbert order at `level+1`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level + 1 > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{children, coarsen};
+/// const D: usize = 3;
+/// 
+/// let level = 2;
+/// assert_eq!( 40..48, children::<D>(5, level) );
+/// for child in children::<D>(5, level) {
+///     assert_eq!( 5, coarsen::<D>(child, level + 1, level) );
+/// }
+/// ```
+/// 
+pub fn children<const D: usize>(h: usize, level: usize) -> Range<usize> {
+    assert!(level < max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level + 1, D, max_level::<D>());
+    (h << D)..((h + 1) << D)
+}
+
+/// Scale a grid point at `from_level` to the lower corner of the same cell at the finer `to_level`.
+/// 
+/// Each component is multiplied by `2.pow(to_level - from_level)`.
+/// This is the grid-space counterpart of `children`: the result lies in the cell `p`,
+/// so `coarsen` of its Hilbert index gives the Hilbert index of `p` at `from_level`.
+/// The inverse is `coarsen_point`.
+/// 
+/// # Panics
+/// 
+/// Panics if `to_level < from_level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{coarsen, refine_point, ToHilbertIndex};
+/// const D: usize = 2;
+/// 
+/// let p = [2, 3];
+/// let q = refine_point(&p, 2, 4);
+/// assert_eq!( [8, 12], q );
+/// assert_eq!( p.to_hilbert_index(2), coarsen::<D>(q.to_hilbert_index(4), 4, 2) );
+/// ```
+/// 
+pub fn refine_point<const D: usize>(p: &[usize; D], from_level: usize, to_level: usize) -> [usize; D] {
+    assert!(from_level <= to_level, "refine_point: from_level {} exceeds to_level {}", from_level, to_level);
+    let mut q = *p;
+    for x in q.iter_mut() {
+        *x <<= to_level - from_level;
+    }
+    q
+}
+
+/// The grid point at the coarser `to_level` of the cell which contains the grid point `p` at `from_level`.
+/// 
+/// Each component is divided by `2.pow(from_level - to_level)`.
+/// This is the grid-space counterpart of `coarsen`, i.e. the Hilbert index of the result is
+/// `coarsen::<D>(p.to_hilbert_index(from_level), from_level, to_level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `to_level > from_level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{coarsen, coarsen_point, ToHilbertIndex};
+/// const D: usize = 2;
+/// 
+/// let p = [5, 6];
+/// assert_eq!( [2, 3], coarsen_point(&p, 3, 2) );
+/// assert_eq!( [2, 3].to_hilbert_index(2), coarsen::<D>(p.to_hilbert_index(3), 3, 2) );
+/// ```
+/// 
+pub fn coarsen_point<const D: usize>(p: &[usize; D], from_level: usize, to_level: usize) -> [usize; D] {
+    assert!(to_level <= from_level, "coarsen_point: to_level {} exceeds from_level {}", to_level, from_level);
+    let mut q = *p;
+    for x in q.iter_mut() {
+        *x = x.checked_shr((from_level - to_level) as u32).unwrap_or(0);
+    }
+    q
+}
+
+/// The number of cells at all levels below `level`, i.e. `1 + 2.pow(D) + .. + 2.pow(D*(level-1))`.
+/// 
+/// This is the offset which numbers the cells of all levels in a single sequence:
+/// the cell `h` at `level` gets `level_offset::<D>(level) + h`, so that
+/// level `0` comes first, followed by level `1`, and so on, each level in the Hilbert order.
+/// Such a numbering is used e.g. by linear octrees. The inverse is `split_level_offset`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::level_offset;
+/// 
+/// assert_eq!( 0, level_offset::<3>(0) );
+/// assert_eq!( 1, level_offset::<3>(1) );
+/// assert_eq!( 1 + 8 + 64, level_offset::<3>(3) );
+/// ```
+/// 
+pub fn level_offset<const D: usize>(level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    (0..level).fold(0, |ofs, _| {
+        (ofs << D) | 1
+    })
+}
+
+/// Split a number given by `level_offset::<D>(level) + h` into the Hilbert index `h` and the `level`.
+/// 
+/// # Panics
+/// 
+/// Panics if the level exceeds `max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{level_offset, split_level_offset};
+/// 
+/// assert_eq!( (0, 0), split_level_offset::<3>(0) );
+/// assert_eq!( (5, 2), split_level_offset::<3>(level_offset::<3>(2) + 5) );
+/// ```
+/// 
+pub fn split_level_offset<const D: usize>(n: usize) -> (usize, usize) {
+    let (mut level, mut ofs) = (0, 0);
+    // 次の level の offset は ofs + 2^(D*level)
+    while n - ofs >= num_cells::<D>(level) {
+        ofs += num_cells::<D>(level);
+        level += 1;
+        assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    }
+    (n - ofs, level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{children, coarsen, coarsen_point, indices, level_offset, max_level, points, refine_point, split_level_offset, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        for h in indices::<D>(level) {
+            let parent: [usize; D] = h.from_hilbert_index(level);
+            let mut n = 0;
+            for child in children::<D>(h, level) {
+                assert_eq!(coarsen::<D>(child, level + 1, level), h);
+
+                let p: [usize; D] = child.from_hilbert_index(level + 1);
+                for (&x, &y) in p.iter().zip(parent.iter()) {
+                    assert_eq!(x >> 1, y);
+                }
+                n += 1;
+            }
+            assert_eq!(n, 1 << D);
+
+            let finer = level + 2;
+            for child in children::<D>(h, level).flat_map(|c| children::<D>(c, level + 1)) {
+                assert_eq!(coarsen::<D>(child, finer, level), h);
+            }
+        }
+    }
+
+    #[test]
+    fn children_and_coarsen() {
+        for level in 0..4 {
+            check::<2>(level);
+            check::<3>(level);
+        }
+        check::<4>(2);
+        assert_eq!(coarsen::<3>(123, 2, 2), 123);
+        assert_eq!(coarsen::<3>(usize::MAX, 30, 0), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn coarsen_to_finer_level() {
+        coarsen::<2>(0, 1, 2);
+    }
+
+    #[test]
+    fn offset() {
+        assert_eq!(level_offset::<3>(2), 9);
+        assert_eq!(level_offset::<2>(4), 1 + 4 + 16 + 64);
+
+        // 全ての level の cell に重複なく番号が振られる
+        let mut n = 0;
+        for level in 0..4 {
+            assert_eq!(level_offset::<3>(level), n);
+            for h in indices::<3>(level) {
+                assert_eq!(split_level_offset::<3>(n), (h, level));
+                n += 1;
+            }
+        }
+
+        let level = max_level::<3>();
+        let last = level_offset::<3>(level) + (1 << (3*level)) - 1;
+        assert_eq!(split_level_offset::<3>(last), ((1 << (3*level)) - 1, level));
+    }
+
+    #[test]
+    fn refine_and_coarsen_point() {
+        let level = 3;
+        for p in points::<3>(level) {
+            for to_level in level..level + 3 {
+                let q = refine_point(&p, level, to_level);
+                assert_eq!(coarsen_point(&q, to_level, level), p);
+                assert_eq!(coarsen::<3>(q.to_hilbert_index(to_level), to_level, level), p.to_hilbert_index(level));
+            }
+            for to_level in 0..=level {
+                let q = coarsen_point(&p, level, to_level);
+                assert_eq!(q.to_hilbert_index(to_level), coarsen::<3>(p.to_hilbert_index(level), level, to_level));
+            }
+        }
+        assert_eq!(coarsen_point(&[usize::MAX, 1], 64, 0), [0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "from_level 3 exceeds to_level 2")]
+    fn refine_to_coarser() {
+        refine_point(&[1, 2], 3, 2);
+    }
+}
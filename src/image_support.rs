@@ -0,0 +1,121 @@
+use core::iter::FusedIterator;
+use image::GenericImageView;
+use crate::FromHilbertIndex;
+
+/// The pixels of an image in the Hilbert order, as `(x, y, pixel)` (requires the `image` feature).
+/// 
+/// The image of size `w x h` is covered by the box of size `2.pow(lx) x 2.pow(ly)`,
+/// where `2.pow(lx)` and `2.pow(ly)` are the next powers of two of `w` and `h`.
+/// The cells of the box are visited along its compact Hilbert curve (see `to_compact_hilbert_index`)
+/// with the grid point `[x, y]`, skipping the cells out of the image.
+/// Hence every pixel is yielded exactly once, and a wide image does not visit a square box of its width.
+/// 
+/// # Panics
+/// 
+/// Panics if the box has `2.pow(usize::BITS)` or more cells.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::hilbert_pixels;
+/// use image::{GrayImage, Luma};
+/// 
+/// let img = GrayImage::from_fn(5, 3, |x, y| Luma([(x + 5*y) as u8]));
+/// let mut pixels = hilbert_pixels(&img);
+/// assert_eq!( 15, pixels.len() );
+/// assert_eq!( Some((0, 0, Luma([0]))), pixels.next() );
+/// assert_eq!( Some((0, 1, Luma([5]))), pixels.next() );
+/// ```
+/// 
+pub fn hilbert_pixels<I: GenericImageView + ?Sized>(img: &I) -> HilbertPixels<'_, I> {
+    let (w, h) = img.dimensions();
+    let levels = [level(w), level(h)];
+    let bits = levels[0] + levels[1];
+    assert!(bits < usize::BITS as usize, "image of {}x{} needs {} bits, which do not fit in usize", w, h, bits);
+
+    let remaining = w as usize * h as usize;
+    HilbertPixels {
+        img,
+        levels,
+        h: 0,
+        end: if remaining == 0 { 0 } else { 1 << bits },
+        remaining,
+    }
+}
+
+// 0..n の座標を表すのに必要な bit 数
+#[inline]
+fn level(n: u32) -> usize {
+    (u32::BITS - n.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// An iterator over the pixels of an image in the Hilbert order, created by `hilbert_pixels`.
+#[derive(Debug, Clone)]
+pub struct HilbertPixels<'a, I: ?Sized> {
+    img: &'a I,
+    levels: [usize; 2],
+    h: usize,
+    end: usize,
+    remaining: usize,
+}
+
+impl<'a, I: GenericImageView + ?Sized> Iterator for HilbertPixels<'a, I> {
+    type Item = (u32, u32, I::Pixel);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (w, h) = self.img.dimensions();
+        while self.h < self.end {
+            let [x, y]: [usize; 2] = self.h.from_compact_hilbert_index(self.levels);
+            self.h += 1;
+            if x < w as usize && y < h as usize {
+                self.remaining -= 1;
+                let (x, y) = (x as u32, y as u32);
+                return Some((x, y, self.img.get_pixel(x, y)));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, I: GenericImageView + ?Sized> ExactSizeIterator for HilbertPixels<'a, I> {}
+impl<'a, I: GenericImageView + ?Sized> FusedIterator for HilbertPixels<'a, I> {}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, Luma, RgbImage};
+    use crate::{hilbert_pixels, ToHilbertIndex};
+
+    #[test]
+    fn every_pixel_once() {
+        let img = GrayImage::from_fn(5, 3, |x, y| Luma([(x + 5*y) as u8]));
+        let mut seen = [false; 15];
+        let mut prv = None;
+        for (x, y, pixel) in hilbert_pixels(&img) {
+            assert_eq!(pixel, Luma([(x + 5*y) as u8]));
+            assert!(!seen[(x + 5*y) as usize]);
+            seen[(x + 5*y) as usize] = true;
+
+            // 8x4 の compact Hilbert curve の順に並ぶ
+            let h = [x as usize, y as usize].to_compact_hilbert_index([3, 2]);
+            if let Some(prv) = prv { assert!(prv < h); }
+            prv = Some(h);
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn sizes() {
+        for &(w, h) in [(1, 1), (4, 4), (7, 1), (1, 9), (100, 3), (16, 17)].iter() {
+            let img = RgbImage::new(w, h);
+            let pixels = hilbert_pixels(&img);
+            assert_eq!(pixels.len(), (w*h) as usize);
+            assert_eq!(pixels.count(), (w*h) as usize);
+        }
+        assert_eq!(hilbert_pixels(&RgbImage::new(0, 5)).count(), 0);
+        assert_eq!(hilbert_pixels(&RgbImage::new(5, 0)).len(), 0);
+    }
+}
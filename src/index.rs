@@ -0,0 +1,67 @@
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// A Hilbert index which carries its dimension `D` in the type.
+/// 
+/// With the `serde` feature, it is serialized as a plain integer.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertIndex;
+/// 
+/// let level = 2;
+/// let h = HilbertIndex::<2>::from_point(&[1, 1], level);
+/// assert_eq!( HilbertIndex::new(2), h );
+/// assert_eq!( [1, 1], h.to_point(level) );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct HilbertIndex<const D: usize>(pub usize);
+
+impl<const D: usize> HilbertIndex<D> {
+    /// Wrap a raw Hilbert index.
+    pub fn new(h: usize) -> Self {
+        HilbertIndex(h)
+    }
+
+    /// Compute the Hilbert index of a grid point.
+    pub fn from_point(p: &[usize; D], level: usize) -> Self {
+        HilbertIndex(p.to_hilbert_index(level))
+    }
+
+    /// Convert to the grid point.
+    pub fn to_point(&self, level: usize) -> [usize; D] {
+        self.0.from_hilbert_index(level)
+    }
+}
+
+impl<const D: usize> From<HilbertIndex<D>> for usize {
+    fn from(h: HilbertIndex<D>) -> usize {
+        h.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, HilbertIndex};
+
+    #[test]
+    fn round_trip() {
+        let level = 3;
+        for h in indices::<3>(level) {
+            let p = HilbertIndex::<3>::new(h).to_point(level);
+            assert_eq!(HilbertIndex::from_point(&p, level), HilbertIndex(h));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_as_integer() {
+        let json = serde_json::to_string(&HilbertIndex::<3>(42)).unwrap();
+        assert_eq!(json, "42");
+        let h: HilbertIndex<3> = serde_json::from_str(&json).unwrap();
+        assert_eq!(h, HilbertIndex(42));
+    }
+}
@@ -0,0 +1,152 @@
+mod private {
+    pub trait Sealed {}
+}
+
+/// An unsigned integer type which can hold a Hilbert index.
+/// 
+/// This trait is sealed and implemented for `u16`, `u32`, `u64`, `u128`, and `usize`,
+/// and also for `num_bigint::BigUint` with the `num-bigint` feature.
+/// A `D`-dimensional Hilbert index with level `l` fits in `T` if `D*l <= T::BITS`.
+/// 
+/// Only the accumulation of the index depends on the integer type.
+/// The bit operations at each level (Gray code, rotation within `D` bits) act on
+/// `D`-bit words, which are always computed in `usize` with the mask `2.pow(D) - 1`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_as, ToHilbertIndex};
+/// 
+/// let level = 8;
+/// let hindex: u16 = [200, 17].to_hilbert_index_as(level);
+/// assert_eq!( [200, 17], from_hilbert_index_as(hindex, level) );
+/// ```
+/// 
+pub trait HilbertInt: Sized + private::Sealed {
+    /// The number of bits of the integer type.
+    const BITS: usize;
+
+    /// Zero.
+    fn zero() -> Self;
+
+    /// Append a `bits`-bit word `w` to the least significant side, i.e. `(self << bits) | w`.
+    fn push_word(self, w: usize, bits: usize) -> Self;
+
+    /// Extract `bits` bits from the `shift`-th bit, i.e. `(self >> shift) & (2.pow(bits) - 1)`.
+    fn word(&self, shift: usize, bits: usize) -> usize;
+}
+
+macro_rules! impl_hilbert_int {
+    ($($t:ty),*) => {$(
+        impl private::Sealed for $t {}
+
+        impl HilbertInt for $t {
+            const BITS: usize = 8*core::mem::size_of::<$t>();
+
+            #[inline]
+            fn zero() -> Self { 0 }
+
+            #[inline]
+            fn push_word(self, w: usize, bits: usize) -> Self {
+                self.checked_shl(bits as u32).unwrap_or(0) | w as $t
+            }
+
+            #[inline]
+            fn word(&self, shift: usize, bits: usize) -> usize {
+                (self >> shift) as usize & !( {usize::MAX}<<bits )
+            }
+        }
+    )*};
+}
+
+impl_hilbert_int!(u16, u32, u64, u128, usize);
+
+#[cfg(feature = "num-bigint")]
+impl private::Sealed for num_bigint::BigUint {}
+
+#[cfg(feature = "num-bigint")]
+impl HilbertInt for num_bigint::BigUint {
+    // 任意精度なので上限はない
+    const BITS: usize = usize::MAX;
+
+    #[inline]
+    fn zero() -> Self { num_bigint::BigUint::default() }
+
+    #[inline]
+    fn push_word(self, w: usize, bits: usize) -> Self {
+        (self << bits) | num_bigint::BigUint::from(w)
+    }
+
+    #[inline]
+    fn word(&self, shift: usize, bits: usize) -> usize {
+        (0..bits).fold(0, |w, k| w|( (self.bit((shift + k) as u64) as usize) << k ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_as, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        for key in 0..2usize.pow((D*level) as u32) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+
+            assert_eq!(key as u16, xyz.to_hilbert_index_as::<u16>(level));
+            assert_eq!(key as u32, xyz.to_hilbert_index_as::<u32>(level));
+            assert_eq!(key as u64, xyz.to_hilbert_index_as::<u64>(level));
+            assert_eq!(key as u128, xyz.to_hilbert_index_as::<u128>(level));
+            assert_eq!(key, xyz.to_hilbert_index_as::<usize>(level));
+
+            assert_eq!(xyz, from_hilbert_index_as(key as u16, level));
+            assert_eq!(xyz, from_hilbert_index_as(key as u32, level));
+            assert_eq!(xyz, from_hilbert_index_as(key as u64, level));
+            assert_eq!(xyz, from_hilbert_index_as(key as u128, level));
+            assert_eq!(xyz, from_hilbert_index_as(key, level));
+        }
+    }
+
+    #[test]
+    fn same_for_all_widths() {
+        for level in 0..9 { check::<2>(level); }
+        for level in 0..6 { check::<3>(level); }
+        for level in 0..5 { check::<4>(level); }
+    }
+
+    #[test]
+    fn u128_beyond_u64() {
+        let level = 40;
+        let p = [(1 << 40) - 1, 12345, 1 << 39];
+        let h: u128 = p.to_hilbert_index_as(level);
+        assert!(h > u64::MAX as u128);
+        assert_eq!(p, from_hilbert_index_as(h, level));
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn biguint() {
+        use crate::from_hilbert_index_big;
+        use num_bigint::BigUint;
+
+        const D: usize = 8;
+        let level = 20;
+        let points = [
+            [0; D],
+            [(1 << 20) - 1; D],
+            [1, 2, 3, 4, 5, 6, 7, 8],
+            [1 << 19, 0, 54321, 999999, 3, 1 << 18, 12, 65536],
+        ];
+        for p in points.iter() {
+            let h: BigUint = p.to_hilbert_index_big(level);
+            assert!(h.bits() <= (D*level) as u64);
+            assert_eq!(*p, from_hilbert_index_big(&h, level));
+        }
+
+        // 小さい level では usize の結果と一致する
+        let level = 3;
+        for key in 0..2usize.pow((3*level) as u32) {
+            let xyz: [usize; 3] = key.from_hilbert_index(level);
+            assert_eq!(BigUint::from(key), xyz.to_hilbert_index_big(level));
+            assert_eq!(xyz, from_hilbert_index_big(&BigUint::from(key), level));
+        }
+    }
+}
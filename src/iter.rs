@@ -0,0 +1,1040 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+use crate::{dmap, emap, gray, max, max_level, num_cells, reduce, t, t_inv, HilbertError};
+
+/// Get an iterator that generates all Hilbert indices for a given level.
+/// 
+/// The return value iterates over the same values as `0..num_cells::<D>(level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`, since the range does not fit in `usize`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{FromHilbertIndex, indices};
+/// const D: usize = 3;
+/// 
+/// let level = 4;
+/// 
+/// for hindex in indices::<D>(level) {
+///     let x: [usize; D] = hindex.from_hindex(level);
+///     println!("{:?}", x);
+/// }
+/// ```
+/// 
+pub fn indices<const D: usize>(level: usize) -> Indices<D> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    Indices { range: 0..num_cells::<D>(level), level }
+}
+
+/// Get an iterator that generates all Hilbert indices for a given level, or an error if they do not fit in `usize`.
+/// 
+/// This is the checked version of `indices`, which returns
+/// `Err(HilbertError::LevelOutOfRange { .. })` instead of panicking if `level > max_level::<D>()`,
+/// i.e. `D*level >= usize::BITS`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{try_indices, HilbertError};
+/// 
+/// assert_eq!( 32768, try_indices::<3>(5).unwrap().len() );
+/// #[cfg(target_pointer_width = "64")]
+/// assert_eq!( Some(HilbertError::LevelOutOfRange { level: 10, max_level: 7 }), try_indices::<8>(10).err() );
+/// ```
+/// 
+pub fn try_indices<const D: usize>(level: usize) -> Result<Indices<D>, HilbertError> {
+    crate::level::check_level::<D>(level)?;
+    Ok(indices::<D>(level))
+}
+
+/// Get an iterator over the Hilbert indices `start..end`, clamped to the curve `0..2.pow(D*level)`.
+/// 
+/// It is empty if `start >= end`. This is useful to resume a traversal or to process a shard of the curve;
+/// the corresponding grid points are given by `points_range`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::indices_range;
+/// 
+/// assert!( indices_range::<2>(3, 10, 20).eq(10..20) );
+/// assert!( indices_range::<2>(3, 60, 100).eq(60..64) );
+/// assert_eq!( 0, indices_range::<2>(3, 20, 10).len() );
+/// ```
+/// 
+pub fn indices_range<const D: usize>(level: usize, start: usize, end: usize) -> Indices<D> {
+    let Indices { range, level } = indices::<D>(level);
+    Indices { range: start.min(range.end)..end.min(range.end), level }
+}
+
+/// An iterator over Hilbert indices, created by `indices` or `indices_range`.
+/// 
+/// It can be reversed by `rev()`, and knows its length by `len()`.
+/// Since it is a named type, it can be stored in a field of a struct without boxing.
+/// 
+/// ```
+/// use hilbert_index::{indices, Indices};
+/// 
+/// assert_eq!( Some(63), indices::<2>(3).rev().next() );
+/// assert_eq!( 64, indices::<2>(3).len() );
+/// 
+/// struct Scan {
+///     indices: Indices<3>,
+/// }
+/// let scan = Scan { indices: indices(2) };
+/// assert_eq!( 2, scan.indices.level() );
+/// ```
+/// 
+#[derive(Debug, Clone)]
+pub struct Indices<const D: usize> {
+    range: core::ops::Range<usize>,
+    level: usize,
+}
+
+impl<const D: usize> Indices<D> {
+    /// The level of the curve.
+    pub fn level(&self) -> usize { self.level }
+}
+
+impl<const D: usize> Iterator for Indices<D> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> { self.range.next() }
+
+    fn size_hint(&self) -> (usize, Option<usize>) { self.range.size_hint() }
+
+    fn nth(&mut self, n: usize) -> Option<usize> { self.range.nth(n) }
+}
+
+impl<const D: usize> DoubleEndedIterator for Indices<D> {
+    fn next_back(&mut self) -> Option<usize> { self.range.next_back() }
+
+    fn nth_back(&mut self, n: usize) -> Option<usize> { self.range.nth_back(n) }
+}
+
+impl<const D: usize> ExactSizeIterator for Indices<D> {}
+
+impl<const D: usize> core::iter::FusedIterator for Indices<D> {}
+
+/// Get an iterator that generates all grid points in the Hilbert order for a given level.
+/// 
+/// This is equivalent to `indices::<D>(level).map(|h| h.from_hilbert_index(level))`,
+/// but faster: the iterator carries the state of the curve at every level forward
+/// and recomputes only the levels that change, so each step costs `O(D)` on average
+/// instead of `O(D*level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::points;
+/// 
+/// let level = 1;
+/// let p: Vec<[usize; 2]> = points(level).collect();
+/// assert_eq!( vec![[0, 0], [0, 1], [1, 1], [1, 0]], p );
+/// ```
+/// 
+pub fn points<const D: usize>(level: usize) -> Points<D> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    Points { walker: Walker::new(0, level), next: 0, end: num_cells::<D>(level) }
+}
+
+/// Get an iterator over the grid points of the Hilbert indices `start..end` in the Hilbert order,
+/// clamped to the curve `0..2.pow(D*level)`.
+/// 
+/// This is equivalent to `indices_range::<D>(level, start, end).map(|h| h.from_hilbert_index(level))`,
+/// and as fast as `points`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{points, points_range};
+/// 
+/// let level = 3;
+/// assert!( points_range::<2>(level, 10, 20).eq(points(level).skip(10).take(10)) );
+/// ```
+/// 
+pub fn points_range<const D: usize>(level: usize, start: usize, end: usize) -> Points<D> {
+    let range = indices_range::<D>(level, start, end).range;
+    Points { walker: Walker::new(range.start, level), next: range.start, end: range.end.max(range.start) }
+}
+
+/// Collect all grid points in the Hilbert order for a given level into a `Vec` (requires the `alloc` feature).
+/// 
+/// The vector is allocated once with the exact capacity `2.pow(D*level)` and filled by `points`,
+/// which is much faster than decoding every index by `from_hilbert_index`.
+/// Note that the vector takes `2.pow(D*level) * D * size_of::<usize>()` bytes,
+/// e.g. 512 MiB for `D = 2` and `level = 12` on 64-bit platforms; use `points` to avoid the materialization.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`, or the allocation fails.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::collect_points;
+/// 
+/// let p = collect_points::<2>(1);
+/// assert_eq!( vec![[0, 0], [0, 1], [1, 1], [1, 0]], p );
+/// assert_eq!( p.len(), p.capacity() );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn collect_points<const D: usize>(level: usize) -> Vec<[usize; D]> {
+    let points = points::<D>(level);
+    let mut v = Vec::with_capacity(num_cells::<D>(level));
+    v.extend(points);
+    v
+}
+
+/// Call `f(p, h)` for every grid point `p` and its Hilbert index `h`, in the Hilbert order.
+/// 
+/// This is `enumerate_points(level).for_each(|(h, p)| f(p, h))` without the iterator,
+/// for callback-oriented code such as FFI shims.
+/// The grid points are computed incrementally as in `points`.
+/// See `try_for_each_point` to stop early.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::for_each_point;
+/// 
+/// let mut p = Vec::new();
+/// for_each_point::<2>(1, |q, h| p.push((h, q)));
+/// assert_eq!( vec![(0, [0, 0]), (1, [0, 1]), (2, [1, 1]), (3, [1, 0])], p );
+/// ```
+/// 
+pub fn for_each_point<const D: usize>(level: usize, mut f: impl FnMut([usize; D], usize)) {
+    let _ = try_for_each_point::<(), D>(level, |p, h| {
+        f(p, h);
+        ControlFlow::Continue(())
+    });
+}
+
+/// Call `f(p, h)` for every grid point `p` and its Hilbert index `h` in the Hilbert order,
+/// until `f` returns `ControlFlow::Break`.
+/// 
+/// Returns the `ControlFlow::Break` returned by `f`, or `ControlFlow::Continue(())` if all the grid points are visited.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use core::ops::ControlFlow;
+/// use hilbert_index::try_for_each_point;
+/// 
+/// // the first point on the diagonal except the origin
+/// let found = try_for_each_point::<_, 2>(3, |p, h| {
+///     if h > 0 && p[0] == p[1] { ControlFlow::Break(h) } else { ControlFlow::Continue(()) }
+/// });
+/// assert_eq!( ControlFlow::Break(2), found );
+/// ```
+/// 
+pub fn try_for_each_point<B, const D: usize>(level: usize, mut f: impl FnMut([usize; D], usize) -> ControlFlow<B>) -> ControlFlow<B> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    let mut walker = Walker::<D>::new(0, level);
+    for h in 0..=last::<D>(level) {
+        walker.seek(h);
+        f(walker.p, h)?;
+    }
+    ControlFlow::Continue(())
+}
+
+/// An iterator over grid points in the Hilbert order, created by `points` or `points_range`.
+#[derive(Debug, Clone)]
+pub struct Points<const D: usize> {
+    walker: Walker<D>,
+    next: usize,
+    end: usize,
+}
+
+impl<const D: usize> Iterator for Points<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        if self.next >= self.end { return None; }
+        self.walker.seek(self.next);
+        self.next += 1;
+        Some(self.walker.p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.next;
+        (n, Some(n))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Points<D> {}
+
+/// Get an iterator over all pairs of Hilbert indices and grid points `(h, p)` in the Hilbert order.
+/// 
+/// This is equivalent to `indices::<D>(level).zip(points(level))`,
+/// and the grid points are computed incrementally as in `points`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::enumerate_points;
+/// 
+/// let level = 1;
+/// let p: Vec<(usize, [usize; 2])> = enumerate_points(level).collect();
+/// assert_eq!( vec![(0, [0, 0]), (1, [0, 1]), (2, [1, 1]), (3, [1, 0])], p );
+/// ```
+/// 
+pub fn enumerate_points<const D: usize>(level: usize) -> EnumeratePoints<D> {
+    EnumeratePoints { points: points(level) }
+}
+
+/// An iterator over pairs of Hilbert indices and grid points, created by `enumerate_points`.
+#[derive(Debug, Clone)]
+pub struct EnumeratePoints<const D: usize> {
+    points: Points<D>,
+}
+
+impl<const D: usize> Iterator for EnumeratePoints<D> {
+    type Item = (usize, [usize; D]);
+
+    fn next(&mut self) -> Option<(usize, [usize; D])> {
+        let h = self.points.next;
+        self.points.next().map(|p| (h, p))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for EnumeratePoints<D> {}
+
+/// Get an iterator over the line segments `(p[i], p[i+1])` of the curve, for plotting.
+/// 
+/// The segments are the consecutive pairs of `points(level)`, so there are `2.pow(D*level) - 1` of them,
+/// and each segment is an axis-aligned unit step.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::segments;
+/// 
+/// let level = 1;
+/// let s: Vec<([usize; 2], [usize; 2])> = segments(level).collect();
+/// assert_eq!( vec![([0, 0], [0, 1]), ([0, 1], [1, 1]), ([1, 1], [1, 0])], s );
+/// ```
+/// 
+pub fn segments<const D: usize>(level: usize) -> Segments<D> {
+    let mut points = points(level);
+    let prev = points.next();
+    Segments { points, prev }
+}
+
+/// An iterator over the line segments of the curve, created by `segments`.
+#[derive(Debug, Clone)]
+pub struct Segments<const D: usize> {
+    points: Points<D>,
+    prev: Option<[usize; D]>,
+}
+
+impl<const D: usize> Iterator for Segments<D> {
+    type Item = ([usize; D], [usize; D]);
+
+    fn next(&mut self) -> Option<([usize; D], [usize; D])> {
+        let p = self.points.next()?;
+        let q = self.prev.replace(p)?;
+        Some((q, p))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.points.size_hint()
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Segments<D> {}
+
+/// Get an iterator over the turning points of the curve, i.e. the vertices of the polyline, for mesh generation.
+/// 
+/// A grid point of `points(level)` is yielded only if the step into it and the step out of it
+/// are in different directions, so straight runs of the curve are collapsed to their ends.
+/// The first and the last points of the curve are always yielded.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::turning_points;
+/// 
+/// let level = 2;
+/// let p: Vec<[usize; 2]> = turning_points(level).collect();
+/// // [0, 0] -> [1, 0] -> [1, 1] -> [0, 1] -> [0, 3], skipping [0, 2] in the straight run, and so on
+/// assert_eq!( &[[0, 0], [1, 0], [1, 1], [0, 1], [0, 3], [1, 3]], &p[..6] );
+/// assert_eq!( Some(&[3, 0]), p.last() );
+/// ```
+/// 
+pub fn turning_points<const D: usize>(level: usize) -> TurningPoints<D> {
+    TurningPoints { points: points(level), prev: None, step: None, pending: false }
+}
+
+/// An iterator over the turning points of the curve, created by `turning_points`.
+#[derive(Debug, Clone)]
+pub struct TurningPoints<const D: usize> {
+    points: Points<D>,
+    // 直前の点と, そこへ入る向き (軸, 正の向きか)
+    prev: Option<[usize; D]>,
+    step: Option<(usize, bool)>,
+    // prev をまだ返していない
+    pending: bool,
+}
+
+impl<const D: usize> Iterator for TurningPoints<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        loop {
+            let q = match self.points.next() {
+                Some(q) => q,
+                // 最後の点
+                None if self.pending => {
+                    self.pending = false;
+                    return self.prev;
+                },
+                None => return None,
+            };
+            let p = match self.prev.replace(q) {
+                Some(p) => p,
+                // 最初の点
+                None => return Some(q),
+            };
+            self.pending = true;
+
+            let k = (0..D).find(|&k| p[k] != q[k]).unwrap_or(0);
+            let step = (k, p[k] < q[k]);
+            if matches!(self.step.replace(step), Some(s) if s != step) {
+                return Some(p);
+            }
+        }
+    }
+}
+
+impl<const D: usize> core::iter::FusedIterator for TurningPoints<D> {}
+
+/// Get a parallel iterator over all grid points in the Hilbert order (requires the `rayon` feature).
+/// 
+/// The index range is split into chunks processed by `rayon`, and each chunk reconstructs
+/// the state of the curve from its first index, then proceeds like `points`.
+/// The items come in the same order as `points`, so `collect` gives the same `Vec`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::par_points;
+/// use rayon::prelude::*;
+/// 
+/// let level = 1;
+/// let p: Vec<[usize; 2]> = par_points(level).collect();
+/// assert_eq!( vec![[0, 0], [0, 1], [1, 1], [1, 0]], p );
+/// ```
+/// 
+#[cfg(feature = "rayon")]
+pub fn par_points<const D: usize>(level: usize) -> impl rayon::iter::IndexedParallelIterator<Item = [usize; D]> {
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    (0..num_cells::<D>(level)).into_par_iter()
+        .map_init(move || Walker::new(0, level), |walker, h| {
+            walker.seek(h);
+            walker.p
+        })
+}
+
+/// The grid point next to `p` along the Hilbert curve, i.e. the point of index `p.to_hilbert_index(level) + 1`.
+/// 
+/// Returns `None` if `p` is the last point of the curve.
+/// The result differs from `p` in exactly one component by `1`.
+/// 
+/// Each call encodes `p` from scratch, which costs `O(D*level)`.
+/// To step repeatedly from a point, use `successors`, which keeps the state of the curve between steps.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::next_point;
+/// 
+/// let level = 1;
+/// assert_eq!( Some([0, 1]), next_point(&[0, 0], level) );
+/// assert_eq!( Some([1, 1]), next_point(&[0, 1], level) );
+/// assert_eq!( None, next_point(&[1, 0], level) );
+/// ```
+/// 
+pub fn next_point<const D: usize>(p: &[usize; D], level: usize) -> Option<[usize; D]> {
+    successors(p, level).next()
+}
+
+/// The grid point previous to `p` along the Hilbert curve, i.e. the point of index `p.to_hilbert_index(level) - 1`.
+/// 
+/// Returns `None` if `p` is the first point of the curve, `[0; D]`.
+/// This is the inverse of `next_point`. To step repeatedly from a point, use `predecessors`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::prev_point;
+/// 
+/// let level = 1;
+/// assert_eq!( Some([0, 0]), prev_point(&[0, 1], level) );
+/// assert_eq!( None, prev_point(&[0, 0], level) );
+/// ```
+/// 
+pub fn prev_point<const D: usize>(p: &[usize; D], level: usize) -> Option<[usize; D]> {
+    predecessors(p, level).next()
+}
+
+/// Get an iterator over the grid points after `p` along the Hilbert curve, i.e. `next_point` applied repeatedly.
+/// 
+/// The state of the curve at every level is computed once from `p`,
+/// and carried between steps as in `points`, so each step costs `O(D)` on average.
+/// The iterator does not yield `p` itself, and ends at the last point of the curve.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{points, successors};
+/// 
+/// let level = 3;
+/// assert_eq!( Some([0, 1]), successors(&[0, 0], level).next() );
+/// assert!( successors::<2>(&[2, 1], level).eq(points(level).skip(8)) );
+/// ```
+/// 
+pub fn successors<const D: usize>(p: &[usize; D], level: usize) -> Successors<D> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    Successors { walker: Walker::from_point(p, level), end: last::<D>(level) }
+}
+
+/// An iterator over the grid points after a given point, created by `successors`.
+#[derive(Debug, Clone)]
+pub struct Successors<const D: usize> {
+    walker: Walker<D>,
+    end: usize,
+}
+
+impl<const D: usize> Iterator for Successors<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        if self.walker.h >= self.end { return None; }
+        self.walker.seek(self.walker.h + 1);
+        Some(self.walker.p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.end - self.walker.h;
+        (n, Some(n))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Successors<D> {}
+
+impl<const D: usize> core::iter::FusedIterator for Successors<D> {}
+
+/// Get an iterator over the grid points before `p` along the Hilbert curve in the reverse order,
+/// i.e. `prev_point` applied repeatedly.
+/// 
+/// As `successors`, each step costs `O(D)` on average.
+/// The iterator does not yield `p` itself, and ends at the first point of the curve `[0; D]`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::predecessors;
+/// 
+/// let level = 1;
+/// let p: Vec<[usize; 2]> = predecessors(&[1, 1], level).collect();
+/// assert_eq!( vec![[0, 1], [0, 0]], p );
+/// ```
+/// 
+pub fn predecessors<const D: usize>(p: &[usize; D], level: usize) -> Predecessors<D> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    Predecessors { walker: Walker::from_point(p, level) }
+}
+
+/// An iterator over the grid points before a given point in the reverse order, created by `predecessors`.
+#[derive(Debug, Clone)]
+pub struct Predecessors<const D: usize> {
+    walker: Walker<D>,
+}
+
+impl<const D: usize> Iterator for Predecessors<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        if self.walker.h == 0 { return None; }
+        self.walker.seek(self.walker.h - 1);
+        Some(self.walker.p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.walker.h, Some(self.walker.h))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for Predecessors<D> {}
+
+impl<const D: usize> core::iter::FusedIterator for Predecessors<D> {}
+
+/// Toroidal version of `next_point`, which wraps around from the last point of the curve to the first point `[0; D]`.
+/// 
+/// The last and the first points of the Hilbert curve are adjacent across the boundary of the box,
+/// so the result always differs from `p` in exactly one component by `1` modulo `2.pow(level)` (for `level >= 1`).
+/// The curve becomes a closed loop on the torus, but it is still not adjacency-preserving as a whole:
+/// cells adjacent across the boundary are generally far apart in the Hilbert order.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::next_point_toroidal;
+/// 
+/// let level = 1;
+/// assert_eq!( [0, 1], next_point_toroidal(&[0, 0], level) );
+/// assert_eq!( [0, 0], next_point_toroidal(&[1, 0], level) );
+/// ```
+/// 
+pub fn next_point_toroidal<const D: usize>(p: &[usize; D], level: usize) -> [usize; D] {
+    next_point(p, level).unwrap_or([0; D])
+}
+
+/// Toroidal version of `prev_point`, which wraps around from the first point of the curve to the last point.
+/// 
+/// This is the inverse of `next_point_toroidal`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::prev_point_toroidal;
+/// 
+/// let level = 1;
+/// assert_eq!( [1, 0], prev_point_toroidal(&[0, 0], level) );
+/// ```
+/// 
+pub fn prev_point_toroidal<const D: usize>(p: &[usize; D], level: usize) -> [usize; D] {
+    prev_point(p, level).unwrap_or_else(|| Walker::<D>::new(last::<D>(level), level).p)
+}
+
+// 曲線の最後の添字 2^(D*level) - 1
+#[inline]
+pub(crate) fn last<const D: usize>(level: usize) -> usize {
+    !( {usize::MAX}.checked_shl((D*level) as u32).unwrap_or(0) )
+}
+
+const BITS: usize = 8*core::mem::size_of::<usize>();
+
+// 各 level の状態 (e, d) を保持したまま曲線上を移動する.
+// 添字が変化した level 以下だけを計算し直す.
+#[derive(Debug, Clone)]
+pub(crate) struct Walker<const D: usize> {
+    level: usize,
+    h: usize,
+    pub(crate) p: [usize; D],
+    // level i を処理する直前の (e, d)
+    e: [usize; BITS],
+    d: [usize; BITS],
+}
+
+impl<const D: usize> Walker<D> {
+    pub(crate) fn new(h: usize, level: usize) -> Self {
+        let mut walker = Walker { level, h, p: [0; D], e: [0; BITS], d: [0; BITS] };
+        walker.update(level);
+        walker
+    }
+
+    // 格子点 p から, 各 level の状態を記録しながら添字を計算する
+    pub(crate) fn from_point(p: &[usize; D], level: usize) -> Self {
+        debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        let mut walker = Walker { level, h: 0, p: *p, e: [0; BITS], d: [0; BITS] };
+        let (mut e, mut d) = (0, 0);
+        for i in (0..level).rev() {
+            walker.e[i] = e;
+            walker.d[i] = d;
+            let w = gray::decode::<D>(t::<D>(reduce(p, i), e, d));
+            e ^= gray::rotate_left::<D>(emap(w), d+1);
+            d = ( d + dmap::<D>(w) + 1 )%D;
+            walker.h = (walker.h << D) | w;
+        }
+        walker
+    }
+
+    // 添字 h の点へ移動する
+    pub(crate) fn seek(&mut self, h: usize) {
+        let diff = self.h ^ h;
+        if diff == 0 { return; }
+        let top = (BITS - diff.leading_zeros() as usize - 1)/D + 1;
+        self.h = h;
+        self.update(top.min(self.level));
+    }
+
+    // level top-1 から 0 までを計算し直す
+    fn update(&mut self, top: usize) {
+        for i in (0..top).rev() {
+            let (e, d) = (self.e[i], self.d[i]);
+            let w = (self.h >> (i*D)) & max::<D>();
+            let l = t_inv::<D>(gray::encode(w), e, d);
+            for (j, x) in self.p.iter_mut().enumerate() {
+                *x = (*x & !(1 << i))|(((l >> j)&1) << i);
+            }
+            if i > 0 {
+                self.e[i-1] = e^gray::rotate_left::<D>( emap(w), d+1 );
+                self.d[i-1] = ( d + dmap::<D>(w) + 1 )%D;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{enumerate_points, indices, max_level, segments, try_indices, turning_points, HilbertError, indices_range, next_point, next_point_toroidal, points, points_range, prev_point, prev_point_toroidal, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        let mut n = 0;
+        let mut prv: Option<[usize; D]> = None;
+        for (key, xyz) in indices::<D>(level).zip(points::<D>(level)) {
+            let expected: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(expected, xyz);
+
+            if let Some(prv) = prv {
+                let diff = prv.iter().zip(xyz.iter())
+                    .map(|(&p, &c)| (p as isize - c as isize).abs())
+                    .sum::<isize>();
+                assert_eq!(diff, 1);
+            }
+            prv = Some(xyz);
+            n += 1;
+        }
+        assert_eq!(n, 2usize.pow((D*level) as u32));
+    }
+
+    #[test]
+    fn for_each() {
+        use crate::{for_each_point, try_for_each_point};
+        use core::ops::ControlFlow;
+
+        fn check<const D: usize>(level: usize) {
+            let mut all = Vec::new();
+            for_each_point::<D>(level, |p, h| all.push((h, p)));
+            assert!(all.iter().copied().eq(enumerate_points::<D>(level)));
+
+            let n = 1 << (D*level);
+            for stop in [0, n/2, n - 1] {
+                let mut visited = 0;
+                let r = try_for_each_point::<_, D>(level, |p, h| {
+                    assert_eq!(p, h.from_hilbert_index(level));
+                    visited += 1;
+                    if h == stop { ControlFlow::Break(p) } else { ControlFlow::Continue(()) }
+                });
+                assert_eq!(r, ControlFlow::Break(stop.from_hilbert_index(level)));
+                assert_eq!(visited, stop + 1);
+            }
+            let r = try_for_each_point::<(), D>(level, |_, _| ControlFlow::Continue(()));
+            assert_eq!(r, ControlFlow::Continue(()));
+        }
+        check::<2>(0);
+        check::<2>(3);
+        check::<3>(2);
+        check::<5>(1);
+    }
+
+    #[test]
+    fn turning() {
+        fn check<const D: usize>(level: usize) {
+            let all: Vec<[usize; D]> = points(level).collect();
+            let turns: Vec<[usize; D]> = turning_points(level).collect();
+            assert_eq!(turns.first(), all.first());
+            assert_eq!(turns.last(), all.last());
+
+            // 連続する折れ点の間は一直線で, 間の点は全て省かれている
+            let mut rest = all.iter();
+            for w in turns.windows(2) {
+                let axes: Vec<usize> = (0..D).filter(|&k| w[0][k] != w[1][k]).collect();
+                assert_eq!(axes.len(), 1);
+                let n = (w[0][axes[0]] as isize - w[1][axes[0]] as isize).unsigned_abs();
+                assert!(rest.by_ref().any(|p| *p == w[0]));
+                assert_eq!(rest.clone().nth(n - 1), Some(&w[1]));
+            }
+            // 省かれた点では曲がらない
+            for w in all.windows(3) {
+                let straight = (0..D).all(|k| w[0][k] + w[2][k] == 2*w[1][k]);
+                assert_eq!(!straight, turns.contains(&w[1]), "{:?}", w);
+            }
+        }
+        check::<2>(0);
+        check::<2>(1);
+        check::<2>(4);
+        check::<3>(3);
+        check::<1>(3);
+
+        assert_eq!(turning_points::<1>(3).collect::<Vec<_>>(), vec![[0], [7]]);
+        assert_eq!(turning_points::<2>(0).collect::<Vec<_>>(), vec![[0, 0]]);
+        assert!(turning_points::<2>(4).count() < 256);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collect() {
+        fn check<const D: usize>(level: usize) {
+            let p = crate::collect_points::<D>(level);
+            assert_eq!(p.len(), 1 << (D*level));
+            assert_eq!(p.capacity(), p.len());
+            for (h, q) in p.iter().enumerate() {
+                assert_eq!(*q, h.from_hilbert_index(level));
+            }
+        }
+        check::<2>(0);
+        check::<2>(4);
+        check::<3>(3);
+        check::<5>(2);
+    }
+
+    #[test]
+    fn same_as_from_hilbert_index() {
+        for level in 0..7 { check::<2>(level); }
+        for level in 0..5 { check::<3>(level); }
+        for level in 0..4 { check::<4>(level); }
+        for level in 0..3 { check::<6>(level); }
+    }
+
+    #[test]
+    fn checked_indices() {
+        assert!(try_indices::<3>(5).unwrap().eq(indices::<3>(5)));
+        assert!(try_indices::<3>(max_level::<3>()).is_ok());
+        assert_eq!(
+            try_indices::<3>(max_level::<3>() + 1).unwrap_err(),
+            HilbertError::LevelOutOfRange { level: max_level::<3>() + 1, max_level: max_level::<3>() },
+        );
+        #[cfg(target_pointer_width = "64")]
+        assert!(try_indices::<8>(10).is_err());
+        assert!(try_indices::<2>(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn indices_double_ended() {
+        assert_eq!(indices::<2>(3).next_back(), Some(63));
+        assert_eq!(indices::<2>(3).len(), 64);
+
+        let forward: Vec<usize> = indices::<3>(2).collect();
+        let mut backward: Vec<usize> = indices::<3>(2).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_points_same_as_points() {
+        use rayon::iter::ParallelIterator;
+        use crate::par_points;
+
+        let seq: Vec<[usize; 3]> = points(6).collect();
+        let par: Vec<[usize; 3]> = par_points(6).collect();
+        assert_eq!(seq, par);
+
+        let seq: Vec<[usize; 2]> = points(9).collect();
+        let par: Vec<[usize; 2]> = par_points(9).collect();
+        assert_eq!(seq, par);
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(indices_range::<2>(3, 10, 20).collect::<Vec<_>>(), (10..20).collect::<Vec<_>>());
+        assert_eq!(indices_range::<2>(3, 10, 20).len(), 10);
+        assert_eq!(indices_range::<2>(3, 60, usize::MAX).collect::<Vec<_>>(), vec![60, 61, 62, 63]);
+        assert_eq!(indices_range::<2>(3, 70, 80).next(), None);
+        assert_eq!(indices_range::<2>(3, 20, 10).next(), None);
+        assert_eq!(indices_range::<3>(2, 5, 9).next_back(), Some(8));
+        assert_eq!(indices_range::<3>(2, 5, 9).level(), 2);
+        assert_eq!(indices::<3>(4).level(), 4);
+
+        let level = 4;
+        for &(start, end) in [(0, 256), (10, 20), (63, 129), (200, 1000), (5, 5), (9, 3)].iter() {
+            let expected: Vec<[usize; 2]> = indices_range::<2>(level, start, end)
+                .map(|h| h.from_hilbert_index(level))
+                .collect();
+            let it = points_range::<2>(level, start, end);
+            assert_eq!(it.len(), expected.len());
+            assert_eq!(it.collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn enumerate() {
+        let level = 3;
+        let mut n = 0;
+        for (i, (h, p)) in enumerate_points::<3>(level).enumerate() {
+            assert_eq!(h, i);
+            assert_eq!(p, h.from_hilbert_index(level));
+            n += 1;
+        }
+        assert_eq!(n, 512);
+        assert_eq!(enumerate_points::<2>(4).len(), 256);
+        assert_eq!(enumerate_points::<2>(0).collect::<Vec<_>>(), vec![(0, [0, 0])]);
+    }
+
+    #[test]
+    fn unit_segments() {
+        let s: Vec<_> = segments::<2>(2).collect();
+        assert_eq!(s.len(), 15);
+        for (p, q) in s.iter() {
+            let diff: usize = p.iter().zip(q.iter())
+                .map(|(&a, &b)| (a as isize - b as isize).unsigned_abs())
+                .sum();
+            assert_eq!(diff, 1);
+        }
+        assert!(s.windows(2).all(|w| w[0].1 == w[1].0));
+        assert_eq!(segments::<3>(3).len(), 511);
+        assert_eq!(segments::<3>(0).next(), None);
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut it = points::<3>(2);
+        assert_eq!(it.len(), 64);
+        it.next();
+        assert_eq!(it.len(), 63);
+    }
+
+    fn check_walk<const D: usize>(level: usize) {
+        use crate::{predecessors, successors};
+
+        let n = 1 << (D*level);
+        for h in [0, n/3, n/2, n - 1] {
+            let p: [usize; D] = h.from_hilbert_index(level);
+            let after = successors(&p, level);
+            assert_eq!(after.len(), n - 1 - h);
+            assert!(after.eq(points_range::<D>(level, h + 1, n)));
+
+            let before = predecessors(&p, level);
+            assert_eq!(before.len(), h);
+            assert!(before.eq(indices::<D>(level).take(h).rev().map(|k| k.from_hilbert_index(level))));
+        }
+    }
+
+    #[test]
+    fn walk_from_point() {
+        check_walk::<2>(0);
+        check_walk::<2>(4);
+        check_walk::<3>(3);
+        check_walk::<5>(2);
+
+        let mut it = crate::successors(&[1, 0], 1);
+        assert_eq!((it.next(), it.next()), (None, None));
+        let mut it = crate::predecessors(&[0, 0], 1);
+        assert_eq!((it.next(), it.next()), (None, None));
+    }
+
+    #[test]
+    fn next_and_prev_point() {
+        const D: usize = 3;
+        let level = 4;
+        let n = 1 << (D*level);
+        for key in 0..n {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            let next = next_point(&xyz, level);
+            if key + 1 < n {
+                let expected: [usize; D] = (key + 1).from_hilbert_index(level);
+                assert_eq!(Some(expected), next);
+
+                // ちょうど 1 つの成分だけが \pm 1 違う
+                let changed = xyz.iter().zip(expected.iter())
+                    .filter(|(a, b)| a != b)
+                    .map(|(&a, &b)| (a as isize - b as isize).abs())
+                    .collect::<Vec<_>>();
+                assert_eq!(changed, vec![1]);
+            } else {
+                assert_eq!(None, next);
+            }
+
+            let prev = prev_point(&xyz, level);
+            if key > 0 {
+                assert_eq!(Some((key - 1).from_hilbert_index(level)), prev);
+            } else {
+                assert_eq!(None, prev);
+            }
+        }
+
+        assert_eq!(None, next_point(&[0; 2], 0));
+        assert_eq!(None, prev_point(&[0; 2], 0));
+        let p = [3, 1, 4, 1, 5];
+        assert_eq!(p, prev_point(&next_point(&p, 3).unwrap(), 3).unwrap());
+        assert_eq!(next_point(&p, 3).unwrap().to_hilbert_index(3), p.to_hilbert_index(3) + 1);
+    }
+
+    fn check_toroidal<const D: usize>(level: usize) {
+        let bound: usize = 1 << level;
+        let first = [0; D];
+        let last: [usize; D] = (bound.pow(D as u32) - 1).from_hilbert_index(level);
+        assert_eq!(first, next_point_toroidal(&last, level));
+        assert_eq!(last, prev_point_toroidal(&first, level));
+
+        // 周期境界の下で 1 つの成分だけが \pm 1 違う
+        let changed = first.iter().zip(last.iter())
+            .filter(|(a, b)| a != b)
+            .map(|(&a, &b)| (a + bound - b)%bound)
+            .collect::<Vec<_>>();
+        assert!(changed == vec![1] || changed == vec![bound - 1]);
+
+        let p: [usize; D] = 5.from_hilbert_index(level);
+        assert_eq!(next_point(&p, level), Some(next_point_toroidal(&p, level)));
+        assert_eq!(prev_point(&p, level), Some(prev_point_toroidal(&p, level)));
+    }
+
+    #[test]
+    fn toroidal_step() {
+        for level in 1..6 {
+            check_toroidal::<2>(level);
+            check_toroidal::<3>(level);
+            check_toroidal::<5>(level);
+        }
+        assert_eq!([0; 3], next_point_toroidal(&[0; 3], 0));
+        assert_eq!([0; 3], prev_point_toroidal(&[0; 3], 0));
+    }
+}
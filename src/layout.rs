@@ -0,0 +1,172 @@
+use crate::{axis_extent, max_level, num_cells, FromHilbertIndex, ToHilbertIndex};
+
+/// Layout of a square matrix in a flat buffer in the Hilbert order.
+/// 
+/// The element at `(row, col)` is stored at the offset given by the 2D Hilbert index of `[row, col]`,
+/// so that nearby elements are nearby in memory at every scale.
+/// This is useful for cache-oblivious algorithms such as matrix transposition and multiplication.
+/// The matrix must be padded to a square of side `2.pow(level)`, and the buffer has `len()` elements.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertLayout2D;
+/// 
+/// let layout = HilbertLayout2D::new(2);
+/// let mut buffer = vec![0.0; layout.len()];
+/// buffer[layout.offset(1, 3)] = 1.0;
+/// assert_eq!( 16, buffer.len() );
+/// assert_eq!( (1, 3), layout.coords(layout.offset(1, 3)) );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HilbertLayout2D {
+    /// Level of the curve; the matrix is `2.pow(level) x 2.pow(level)`.
+    pub level: usize,
+}
+
+impl HilbertLayout2D {
+    /// Create the layout of a `2.pow(level) x 2.pow(level)` matrix.
+    pub fn new(level: usize) -> Self {
+        HilbertLayout2D { level }
+    }
+
+    /// The number of rows (and columns) of the matrix, i.e. `2.pow(level)`.
+    pub fn side(&self) -> usize { axis_extent(self.level) }
+
+    /// The number of elements of the matrix, i.e. `2.pow(2*level)`.
+    pub fn len(&self) -> usize { num_cells::<2>(self.level) }
+
+    /// Always `false`, since the matrix has at least one element.
+    pub fn is_empty(&self) -> bool { false }
+
+    /// The offset in the flat buffer of the element at `(row, col)`.
+    #[inline]
+    pub fn offset(&self, row: usize, col: usize) -> usize {
+        [row, col].to_hilbert_index(self.level)
+    }
+
+    /// The `(row, col)` of the element at `offset` in the flat buffer. This is the inverse of `offset`.
+    #[inline]
+    pub fn coords(&self, offset: usize) -> (usize, usize) {
+        let [row, col] = offset.from_hilbert_index(self.level);
+        (row, col)
+    }
+}
+
+/// Convert a Hilbert index to the flat row-major index of the same grid point.
+/// 
+/// The row-major index of a grid point `p` in the box of side `n = 2.pow(level)` is
+/// `p[0]*n.pow(D-1) + p[1]*n.pow(D-2) + ... + p[D-1]`, i.e. the last axis varies fastest.
+/// Both indices run over `0..2.pow(D*level)`, so neither overflows as long as `level <= max_level::<D>()`.
+/// This is useful to reorder a flat buffer in the row-major order into the Hilbert order and back.
+/// The inverse is `rowmajor_to_hilbert`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{hilbert_to_rowmajor, rowmajor_to_hilbert, FromHilbertIndex};
+/// 
+/// let level = 3;
+/// let [x, y] = 21.from_hilbert_index(level);
+/// assert_eq!( x*8 + y, hilbert_to_rowmajor::<2>(21, level) );
+/// assert_eq!( 21, rowmajor_to_hilbert::<2>(x*8 + y, level) );
+/// ```
+/// 
+pub fn hilbert_to_rowmajor<const D: usize>(h: usize, level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    let p: [usize; D] = h.from_hilbert_index(level);
+    p.iter().fold(0, |r, &x| (r << level) | x)
+}
+
+/// Convert a flat row-major index to the Hilbert index of the same grid point.
+/// 
+/// This is the inverse of `hilbert_to_rowmajor`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{rowmajor_to_hilbert, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// // [1, 2] is at 1*4 + 2 in the row-major order
+/// assert_eq!( [1, 2].to_hilbert_index(level), rowmajor_to_hilbert::<2>(6, level) );
+/// ```
+/// 
+pub fn rowmajor_to_hilbert<const D: usize>(r: usize, level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    let mut p = [0; D];
+    for (k, x) in p.iter_mut().enumerate() {
+        *x = (r >> (level*(D-1-k))) & !( {usize::MAX}<<level );
+    }
+    p.to_hilbert_index(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hilbert_to_rowmajor, indices, rowmajor_to_hilbert, FromHilbertIndex, HilbertLayout2D};
+
+    #[test]
+    fn rowmajor_2d() {
+        let level = 3;
+        for h in indices::<2>(level) {
+            let [x, y] = h.from_hilbert_index(level);
+            assert_eq!(hilbert_to_rowmajor::<2>(h, level), x*8 + y);
+            assert_eq!(rowmajor_to_hilbert::<2>(x*8 + y, level), h);
+        }
+    }
+
+    #[test]
+    fn rowmajor_round_trip() {
+        for level in 0..4 {
+            let mut seen = [false; 1 << 9];
+            for h in indices::<3>(level) {
+                let r = hilbert_to_rowmajor::<3>(h, level);
+                let [x, y, z] = h.from_hilbert_index(level);
+                let n = 1 << level;
+                assert_eq!(r, x*n*n + y*n + z);
+                assert!(!seen[r]);
+                seen[r] = true;
+                assert_eq!(rowmajor_to_hilbert::<3>(r, level), h);
+            }
+        }
+        let level = 31;
+        let h = usize::MAX >> 2;
+        assert_eq!(rowmajor_to_hilbert::<2>(hilbert_to_rowmajor::<2>(h, level), level), h);
+    }
+
+    #[test]
+    fn round_trip() {
+        let layout = HilbertLayout2D::new(2);
+        assert_eq!(layout.side(), 4);
+        assert_eq!(layout.len(), 16);
+
+        let matrix = [
+            [ 0,  1,  2,  3],
+            [ 4,  5,  6,  7],
+            [ 8,  9, 10, 11],
+            [12, 13, 14, 15],
+        ];
+        let mut buffer = [usize::MAX; 16];
+        for (row, line) in matrix.iter().enumerate() {
+            for (col, &a) in line.iter().enumerate() {
+                let offset = layout.offset(row, col);
+                assert_eq!(buffer[offset], usize::MAX);
+                buffer[offset] = a;
+                assert_eq!(layout.coords(offset), (row, col));
+            }
+        }
+        for (offset, &a) in buffer.iter().enumerate() {
+            let (row, col) = layout.coords(offset);
+            assert_eq!(matrix[row][col], a);
+        }
+    }
+}
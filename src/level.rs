@@ -0,0 +1,450 @@
+use crate::{HilbertError, ToHilbertIndex};
+
+/// The largest level for which a `D`-dimensional Hilbert curve fits in `usize`.
+/// 
+/// For `level <= max_level::<D>()`, all Hilbert indices `0..2.pow(D*level)` and their number
+/// `2.pow(D*level)` are representable in `usize`, i.e. `D*level < usize::BITS`.
+/// Conversions with a larger level overflow; they are rejected by debug assertions,
+/// the checked conversions such as `try_to_hilbert_index` return `HilbertError::LevelOutOfRange`,
+/// and `indices` always panics.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::max_level;
+/// 
+/// #[cfg(target_pointer_width = "64")]
+/// assert_eq!( 21, max_level::<3>() );
+/// #[cfg(target_pointer_width = "32")]
+/// assert_eq!( 10, max_level::<3>() );
+/// ```
+/// 
+pub const fn max_level<const D: usize>() -> usize {
+    match (8*core::mem::size_of::<usize>() - 1).checked_div(D) {
+        Some(level) => level,
+        None => usize::MAX,
+    }
+}
+
+// checked 版の変換で level が max_level を超えていないか確認する
+pub(crate) fn check_level<const D: usize>(level: usize) -> Result<(), HilbertError> {
+    if level > max_level::<D>() {
+        return Err(HilbertError::LevelOutOfRange { level, max_level: max_level::<D>() });
+    }
+
+    Ok(())
+}
+
+/// The smallest level such that `max_coord < 2.pow(level)`.
+/// 
+/// A grid point whose components are at most `max_coord` can be converted with this level.
+/// `level_for::<D>(0)` is `0`, since the single point `[0; D]` needs no bits.
+/// 
+/// # Panics
+/// 
+/// Panics if the result exceeds `max_level::<D>()`, i.e. such points do not fit in a `usize` Hilbert index.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::level_for;
+/// 
+/// assert_eq!( 10, level_for::<3>(1000) );
+/// assert_eq!( 10, level_for::<3>(1023) );
+/// assert_eq!( 11, level_for::<3>(1024) );
+/// ```
+/// 
+pub fn level_for<const D: usize>(max_coord: usize) -> usize {
+    let level = (usize::BITS - max_coord.leading_zeros()) as usize;
+    assert!(level <= max_level::<D>(), "level {} for {} exceeds max_level::<{}>() = {}", level, max_coord, D, max_level::<D>());
+    level
+}
+
+/// The smallest level which can represent all components of `points`.
+/// 
+/// This is `level_for::<D>` of the largest component, and `0` for an empty slice.
+/// 
+/// # Panics
+/// 
+/// Panics if the result exceeds `max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::level_for_points;
+/// 
+/// assert_eq!( 3, level_for_points(&[[1, 5], [7, 2]]) );
+/// assert_eq!( 0, level_for_points::<2>(&[]) );
+/// ```
+/// 
+pub fn level_for_points<const D: usize>(points: &[[usize; D]]) -> usize {
+    let max_coord = points.iter().flatten().copied().max().unwrap_or(0);
+    level_for::<D>(max_coord)
+}
+
+/// The number of grid points (cells) of a `D`-dimensional Hilbert curve, i.e. `2.pow(D*level)`.
+/// 
+/// This is also the number of Hilbert indices, so that they lie in `0..num_cells::<D>(level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if the result overflows `usize`, i.e. `level > max_level::<D>()`.
+/// See `checked_num_cells` for the non-panicking version.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::num_cells;
+/// 
+/// assert_eq!( 64, num_cells::<3>(2) );
+/// assert_eq!( 1, num_cells::<3>(0) );
+/// ```
+/// 
+pub const fn num_cells<const D: usize>(level: usize) -> usize {
+    match checked_num_cells::<D>(level) {
+        Some(n) => n,
+        None => panic!("num_cells: 2.pow(D*level) overflows usize"),
+    }
+}
+
+/// The number of grid points `2.pow(D*level)`, or `None` if it overflows `usize`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{checked_num_cells, max_level};
+/// 
+/// assert_eq!( Some(64), checked_num_cells::<3>(2) );
+/// assert_eq!( None, checked_num_cells::<3>(max_level::<3>() + 1) );
+/// ```
+/// 
+pub const fn checked_num_cells<const D: usize>(level: usize) -> Option<usize> {
+    match D.checked_mul(level) {
+        Some(bits) => checked_pow2(bits),
+        None => None,
+    }
+}
+
+/// The number of grid points along each axis, i.e. `2.pow(level)`.
+/// 
+/// Each component `x` of grid points satisfies `x < axis_extent(level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if the result overflows `usize`.
+/// See `checked_axis_extent` for the non-panicking version.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::axis_extent;
+/// 
+/// assert_eq!( 8, axis_extent(3) );
+/// ```
+/// 
+pub const fn axis_extent(level: usize) -> usize {
+    match checked_axis_extent(level) {
+        Some(n) => n,
+        None => panic!("axis_extent: 2.pow(level) overflows usize"),
+    }
+}
+
+/// The number of grid points along each axis `2.pow(level)`, or `None` if it overflows `usize`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::checked_axis_extent;
+/// 
+/// assert_eq!( Some(8), checked_axis_extent(3) );
+/// assert_eq!( None, checked_axis_extent(usize::BITS as usize) );
+/// ```
+/// 
+pub const fn checked_axis_extent(level: usize) -> Option<usize> {
+    checked_pow2(level)
+}
+
+// 2^bits, usize に収まらなければ None
+#[inline]
+const fn checked_pow2(bits: usize) -> Option<usize> {
+    if bits < usize::BITS as usize { Some(1 << bits) } else { None }
+}
+
+/// Convert a grid point to a Hilbert index with the smallest level which can represent it.
+/// 
+/// Returns `(level, index)`, where `level = level_for_points(&[*p])` and `index = p.to_hilbert_index(level)`.
+/// Note that indices of points with different levels are not comparable:
+/// the Hilbert order is defined only among indices of the same level.
+/// To compare points, compute a common level with `level_for_points` instead.
+/// 
+/// # Panics
+/// 
+/// Panics if the level exceeds `max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{to_hilbert_index_auto, ToHilbertIndex};
+/// 
+/// assert_eq!( (3, [5, 2].to_hilbert_index(3)), to_hilbert_index_auto(&[5, 2]) );
+/// assert_eq!( (0, 0), to_hilbert_index_auto(&[0, 0]) );
+/// ```
+/// 
+pub fn to_hilbert_index_auto<const D: usize>(p: &[usize; D]) -> (usize, usize) {
+    let level = level_for_points(&[*p]);
+    (level, p.to_hilbert_index(level))
+}
+
+/// The smallest level whose cube encloses an array of a given `shape`, i.e. `2.pow(level) >= shape[k]` for all `k`.
+/// 
+/// This is the largest of `enclosing_compact_levels(shape)`, and grid points with `p[k] < shape[k]`
+/// can be converted with this level. Empty sides (`shape[k] == 0`) are treated as `1`.
+/// 
+/// # Panics
+/// 
+/// Panics if the result exceeds `max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::enclosing_level;
+/// 
+/// assert_eq!( 4, enclosing_level([7, 5, 9]) );
+/// assert_eq!( 3, enclosing_level([8, 8, 8]) );
+/// ```
+/// 
+pub fn enclosing_level<const D: usize>(shape: [usize; D]) -> usize {
+    let max_side = shape.iter().copied().max().unwrap_or(0);
+    level_for::<D>(max_side.saturating_sub(1))
+}
+
+/// The per-axis levels of the smallest box which encloses an array of a given `shape`,
+/// i.e. `levels[k]` is the smallest level with `2.pow(levels[k]) >= shape[k]`.
+/// 
+/// The result is meant for the compact Hilbert indices, `to_compact_hilbert_index(levels)`,
+/// which do not waste bits for non-cubic shapes. Empty sides (`shape[k] == 0`) are treated as `1`.
+/// 
+/// # Panics
+/// 
+/// Panics if the total number of bits `levels.iter().sum()` does not fit in `usize`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{enclosing_compact_levels, ToHilbertIndex};
+/// 
+/// let levels = enclosing_compact_levels([7, 5, 9]);
+/// assert_eq!( [3, 3, 4], levels );
+/// assert!( [6, 4, 8].to_compact_hilbert_index(levels) < 1 << (3 + 3 + 4) );
+/// ```
+/// 
+pub fn enclosing_compact_levels<const D: usize>(shape: [usize; D]) -> [usize; D] {
+    let mut levels = [0; D];
+    for (l, &n) in levels.iter_mut().zip(shape.iter()) {
+        *l = (usize::BITS - n.saturating_sub(1).leading_zeros()) as usize;
+    }
+    let bits: usize = levels.iter().sum();
+    assert!(bits < usize::BITS as usize, "shape {:?} needs {} bits, which do not fit in usize", shape, bits);
+    levels
+}
+
+/// Clamp every component of `p` to `2.pow(level) - 1`, i.e. move it into the grid box of the given level.
+/// 
+/// Out-of-range input becomes the nearest grid point instead of producing a meaningless index.
+/// Use this for lossy tolerance of noisy data, and `try_to_hilbert_index` to reject such input strictly.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{clamp_point, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// assert_eq!( [3, 3], clamp_point([10, 3], level) );
+/// assert!( clamp_point([10, 3], level).try_to_hilbert_index(level).is_ok() );
+/// ```
+/// 
+pub fn clamp_point<const D: usize>(mut p: [usize; D], level: usize) -> [usize; D] {
+    if let Some(extent) = checked_axis_extent(level) {
+        for x in p.iter_mut() {
+            *x = (*x).min(extent - 1);
+        }
+    }
+    p
+}
+
+/// Convert a grid point to a Hilbert index, saturating components at the upper edge of the box.
+/// 
+/// A component `x >= 2.pow(level)` is treated as `2.pow(level) - 1`, i.e. this is `clamp_point` followed by `to_hilbert_index`.
+/// Plain `to_hilbert_index` drops the bits at and above `level`, so a point just on the edge,
+/// such as `x == 2.pow(level)` produced by `(1.0 * 2.pow(level)).floor()`, would wrap around to a wrong cell.
+/// The same rule is applied to real coordinates by `from_unit_coords`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{to_hilbert_index_saturating, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// assert_eq!( [3, 3].to_hilbert_index(level), to_hilbert_index_saturating([4, 3], level) );
+/// assert_eq!( [0, 3].to_hilbert_index(level), [4, 3].to_hilbert_index(level) ); // wrapped
+/// ```
+/// 
+#[inline]
+pub fn to_hilbert_index_saturating<const D: usize>(p: [usize; D], level: usize) -> usize {
+    clamp_point(p, level).to_hilbert_index(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{axis_extent, checked_axis_extent, checked_num_cells, clamp_point, enclosing_compact_levels, enclosing_level, indices, level_for, level_for_points, max_level, num_cells, to_hilbert_index_auto, to_hilbert_index_saturating, FromHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn level_for_coords() {
+        assert_eq!(level_for::<3>(0), 0);
+        assert_eq!(level_for::<3>(1), 1);
+        assert_eq!(level_for::<3>(1000), 10);
+        assert_eq!(level_for::<2>(1 << 30), 31);
+        assert_eq!(level_for_points(&[[3, 1000, 2], [0, 0, 0]]), 10);
+        assert_eq!(level_for_points::<3>(&[]), 0);
+
+        let p = [1000, 999, 3];
+        let level = level_for_points(&[p]);
+        assert!(p.try_to_hilbert_index(level).is_ok());
+        assert!(p.try_to_hilbert_index(level - 1).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn level_for_beyond_max_level() {
+        level_for::<3>(usize::MAX);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn max_level_64bit() {
+        assert_eq!(max_level::<1>(), 63);
+        assert_eq!(max_level::<2>(), 31);
+        assert_eq!(max_level::<3>(), 21);
+        assert_eq!(max_level::<8>(), 7);
+    }
+
+    #[test]
+    fn max_level_fits() {
+        const D: usize = 3;
+        let level = max_level::<D>();
+        let last = 2usize.pow((D*level) as u32) - 1;
+        let p: [usize; D] = last.from_hilbert_index(level);
+        assert_eq!(last, p.to_hilbert_index(level));
+    }
+
+    #[test]
+    #[should_panic]
+    fn indices_beyond_max_level() {
+        let _ = indices::<3>(max_level::<3>() + 1);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn encode_beyond_max_level() {
+        [0; 3].to_hilbert_index(max_level::<3>() + 1);
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn decode_beyond_max_level() {
+        let _: [usize; 3] = 0.from_hilbert_index(max_level::<3>() + 1);
+    }
+
+    #[test]
+    fn cells() {
+        assert_eq!(num_cells::<3>(2), 64);
+        assert_eq!(num_cells::<2>(0), 1);
+        assert_eq!(axis_extent(0), 1);
+        assert_eq!(axis_extent(5), 32);
+        assert_eq!(indices::<3>(3).len(), num_cells::<3>(3));
+
+        // 最大の level までは usize に収まる
+        assert_eq!(checked_num_cells::<3>(max_level::<3>()), Some(1 << (3*max_level::<3>())));
+        assert_eq!(checked_num_cells::<3>(max_level::<3>() + 1), None);
+        assert_eq!(checked_num_cells::<2>(usize::MAX), None);
+        assert_eq!(checked_axis_extent(usize::BITS as usize - 1), Some(1 << (usize::BITS - 1)));
+        assert_eq!(checked_axis_extent(usize::BITS as usize), None);
+
+        const N: usize = num_cells::<2>(3);
+        assert_eq!(N, 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn num_cells_overflow() {
+        num_cells::<3>(max_level::<3>() + 1);
+    }
+
+    #[test]
+    fn clamp() {
+        assert_eq!(clamp_point::<2>([10, 3], 2), [3, 3]);
+        assert_eq!(clamp_point([1, 2, 0], 2), [1, 2, 0]);
+        assert_eq!(clamp_point([5, 0], 0), [0, 0]);
+        assert_eq!(clamp_point([usize::MAX], usize::BITS as usize), [usize::MAX]);
+
+        let level = 3;
+        for p in [[0, 100], [8, 8], [7, 9], [usize::MAX, 2]] {
+            let q = clamp_point(p, level);
+            assert!(q.try_to_hilbert_index(level).is_ok());
+        }
+    }
+
+    #[test]
+    fn saturating_upper_edge() {
+        let level = 3;
+        let last = (1 << (2*level)) - 1;
+        // 上端ちょうどの点は最後の格子に入り, 0 番目の格子に回り込まない
+        assert_eq!(to_hilbert_index_saturating([8, 0], level), [7, 0].to_hilbert_index(level));
+        assert_eq!(to_hilbert_index_saturating([8, 8], level), [7, 7].to_hilbert_index(level));
+        assert_eq!([8, 8].to_hilbert_index(level), 0);
+        assert_eq!(to_hilbert_index_saturating([7, 0], level), last);
+        assert_eq!(to_hilbert_index_saturating([8, 0], level), last);
+        for p in crate::points::<2>(level) {
+            assert_eq!(to_hilbert_index_saturating(p, level), p.to_hilbert_index(level));
+        }
+    }
+
+    #[test]
+    fn enclosing() {
+        assert_eq!(enclosing_level([7, 5, 9]), 4);
+        assert_eq!(enclosing_compact_levels([7, 5, 9]), [3, 3, 4]);
+        assert_eq!(enclosing_level([1, 1]), 0);
+        assert_eq!(enclosing_level([0, 2]), 1);
+        assert_eq!(enclosing_compact_levels([0, 1, 2, 3, 4, 5]), [0, 0, 1, 2, 2, 3]);
+
+        // 配列の全ての要素が変換できる
+        let shape = [7, 5, 9];
+        let (level, levels) = (enclosing_level(shape), enclosing_compact_levels(shape));
+        let p = [6, 4, 8];
+        assert!(p.try_to_hilbert_index(level).is_ok());
+        assert!(p.try_to_hilbert_index(level - 1).is_err());
+        assert_eq!(p, p.to_compact_hilbert_index(levels).from_compact_hilbert_index(levels));
+    }
+
+    #[test]
+    #[should_panic(expected = "do not fit in usize")]
+    fn enclosing_compact_overflow() {
+        enclosing_compact_levels([usize::MAX, 2]);
+    }
+
+    #[test]
+    fn auto_level() {
+        for p in [[0, 0, 0], [1, 0, 0], [5, 2, 7], [8, 0, 3], [1000, 999, 3], [0, 0, 1 << 20]] {
+            let (level, h) = to_hilbert_index_auto(&p);
+            assert_eq!(h, p.to_hilbert_index(level));
+            assert!(p.try_to_hilbert_index(level).is_ok());
+            // 最小の level
+            if level > 0 {
+                assert!(p.try_to_hilbert_index(level - 1).is_err());
+            }
+        }
+        assert_eq!(to_hilbert_index_auto(&[0, 0]), (0, 0));
+    }
+}
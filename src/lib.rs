@@ -5,7 +5,7 @@
 //! 
 //! ## Requirements
 //! 
-//! This crate requires Rust 1.51 or later, due to [const-generics](https://rust-lang.github.io/rfcs/2000-const-generics.html).
+//! This crate requires Rust 1.73 or later, mainly due to [const-generics](https://rust-lang.github.io/rfcs/2000-const-generics.html) and integer methods such as `div_ceil`.
 //! Const-generics enables us to use `[usize; D]` instead of `Vec<usize>`.
 //! 
 //! 
@@ -19,7 +19,12 @@
 //! A `D`-dimensional Hilbert curve with level (order) `l` is a map from indices `0..2.pow(D*l)` to grid points `[usize; D]`,
 //! whose component `x` satisfy `0 <= x < 2.pow(l)`.
 //! Adjacent indices give adjacent grid points.
-//! Input outside the range is not supported and may cause unexpected results.
+//! For `D = 1` the curve is the identity map between `x` and `[x]`.
+//! For `level = 0` the box has the single point `[0; D]`, whose index is `0`.
+//! The dimension must satisfy `1 <= D < usize::BITS`; conversions with other dimensions are rejected at compile time.
+//! Input outside the range is not supported and may cause unexpected results;
+//! use `try_to_hilbert_index` and `try_from_hilbert_index` to reject such input,
+//! or `clamp_point` (`to_hilbert_index_saturating`) to move it to the nearest grid point.
 //! 
 //! The implemented algorithm is based on Butz's algorithm in Chris Hamilton's report, 
 //! "[Compact Hilbert Indices](https://citeseerx.ist.psu.edu/viewdoc/download?doi=10.1.1.133.7490&rep=rep1&type=pdf)".
@@ -29,7 +34,9 @@
 //! ## Usage
 //! 
 //! This crate provides 2 traits, `FromHilbertIndex` and `ToHilbertIndex`.
-//! Additionally, `indices` function gives an iterator that generates all Hilbert indices.
+//! Additionally, `indices` function gives an iterator that generates all Hilbert indices,
+//! and `points` function gives an iterator that generates all grid points in the Hilbert order.
+//! If the dimension is known only at runtime, use `DynHilbert` instead.
 //! 
 //! Convert a index to a grid point.
 //! 
@@ -66,6 +73,36 @@
 //! You can also use `to_hindex` instead of `to_hilbert_index`.
 //! 
 //! 
+//! Grid boxes with unequal side lengths are supported by compact Hilbert indices,
+//! where the `k`-th component has its own level `levels[k]`.
+//! 
+//! ```rust
+//! use hilbert_index::{FromHilbertIndex, ToHilbertIndex};
+//! 
+//! let levels = [3, 1, 2]; // 8 x 2 x 4 box
+//! let hindex = [5, 1, 2].to_compact_hilbert_index(levels);
+//! assert!(hindex < 2usize.pow(3 + 1 + 2));
+//! assert_eq!([5, 1, 2], hindex.from_compact_hilbert_index(levels));
+//! ```
+//! 
+//! 
+//! ## Optional features
+//! 
+//! * `std` (enabled by default): implements `std::error::Error` for `HilbertError`. Implies `alloc`.
+//!   Without this feature the crate is `no_std`, and the conversions between arrays and indices only need `core`.
+//! * `alloc` (enabled by `std`): functions which allocate, such as `neighbors`, `box_to_ranges`, `hilbert_sort` and `DynHilbert::from_index`.
+//! * `num-bigint`: Hilbert indices of arbitrary size as `num_bigint::BigUint` (`to_hilbert_index_big` and `from_hilbert_index_big`).
+//! * `serde`: `Serialize` and `Deserialize` for `HilbertIndex`, as a plain integer.
+//! * `rayon`: `par_points`, a parallel version of `points`.
+//! * `glam`: `ToHilbertIndex` for `glam::UVec2` and `glam::UVec3`, and the inverses `from_hilbert_index_uvec2` and `from_hilbert_index_uvec3`.
+//! * `nalgebra`: `ToHilbertIndex` for `nalgebra::Point<usize, D>` and `nalgebra::SVector<usize, D>`. Only `usize` components are supported;
+//!   other scalar types must be converted first. The inverse is `Point::from(h.from_hilbert_index(level))`.
+//! * `ndarray`: `hilbert_index_order`, the multi-indices of an array of a given shape in the Hilbert order, for traversing `ndarray::ArrayD`. Requires `alloc`.
+//! * `image`: `hilbert_pixels`, the pixels of an `image::GenericImageView` in the Hilbert order, for non-square images as well.
+//! * `geo`: `index_coord` and `coord_from_index`, between `geo::Coord` (or `geo::Point`) in a bounding box and 2D Hilbert indices.
+//! * `wasm`: `HilbertJs`, a `wasm_bindgen` wrapper of `DynHilbert` with `u32` coordinates and `u64` indices for JavaScript. Implies `alloc`.
+//! 
+//! 
 //! ## Similar crates
 //! 
 //! * [hilbert](https://crates.io/crates/hilbert)
@@ -74,90 +111,213 @@
 //! * [fast_hilbert](https://crates.io/crates/fast_hilbert) (only for 2D)
 //! 
 
-/// Get an iterator that generates all Hilbert indices for a given level.
-/// 
-/// The return value is equal to `0..2usize.pow((D*level) as u32)`.
-/// 
-/// # Usage
-/// 
-/// ```
-/// use hilbert_index::{FromHilbertIndex, indices};
-/// const D: usize = 3;
-/// 
-/// let level = 4;
-/// 
-/// for hindex in indices::<D>(level) {
-///     let x: [usize; D] = hindex.from_hindex(level);
-///     println!("{:?}", x);
-/// }
-/// ```
-/// 
-pub fn indices<const D: usize>(level: usize) -> impl std::iter::Iterator<Item=usize> {
-    0..2usize.pow((D*level) as u32)
-}
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-// 基本格子における部分格子の数 2^D - 1
-const fn max<const D: usize>() -> usize { !( {std::usize::MAX}<<D ) }
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-// Gray code
-#[inline]
-fn gc(i: usize) -> usize { i^(i >> 1) }
+mod batch;
+mod bit_order;
+mod bits;
+mod compact;
+mod const_fn;
+mod contains;
+mod coordinate;
+mod corners;
+mod cursor;
+mod digits;
+mod dynamic;
+mod encoder;
+mod error;
+mod generalized;
+#[cfg(feature = "geo")]
+mod geo_support;
+#[cfg(feature = "glam")]
+mod glam_support;
+pub mod gray;
+mod hierarchy;
+#[cfg(feature = "image")]
+mod image_support;
+mod index;
+mod int;
+mod iter;
+mod layout;
+mod level;
+mod locality;
+mod lut;
+mod masked;
+mod moore;
+mod morton;
+#[cfg(feature = "nalgebra")]
+mod nalgebra_support;
+#[cfg(all(feature = "ndarray", feature = "alloc"))]
+mod ndarray_support;
+#[cfg(feature = "alloc")]
+mod neighbors;
+mod ord;
+mod point;
+mod position;
+mod precomputed;
+#[cfg(feature = "alloc")]
+mod query;
+mod reversed;
+mod rtree;
+mod shard;
+mod signed;
+#[cfg(feature = "alloc")]
+mod sort;
+mod space;
+mod state;
+#[cfg(feature = "alloc")]
+mod svg;
+pub mod transform;
+mod tuple;
+pub mod two_d;
+mod unit;
+#[cfg(feature = "wasm")]
+mod wasm;
+pub use batch::{from_hilbert_indices, to_hilbert_indices};
+pub use bit_order::{from_hilbert_index_ordered, to_hilbert_index_ordered, BitOrder};
+pub use bits::{dimension_mask, extract_bit_plane, scatter_bit_plane};
+pub use compact::{compact_to_cube, cube_to_compact};
+pub use const_fn::{from_hilbert_index_const, to_hilbert_index_const};
+pub use contains::box_contains_index;
+pub use coordinate::Coordinate;
+#[cfg(feature = "alloc")]
+pub use corners::corner_points;
+pub use corners::{first_point, last_point, midpoint_point, point_at_fraction};
+pub use cursor::HilbertCursor;
+pub use digits::{from_digits, reverse_digits, subcube_at_depth};
+#[cfg(feature = "alloc")]
+pub use digits::{format_index, index_digits};
+#[cfg(feature = "alloc")]
+pub use dynamic::from_hilbert_index_vec;
+pub use dynamic::DynHilbert;
+pub use encoder::{to_hilbert_index_from_iter, HilbertEncoder};
+pub use error::HilbertError;
+pub use generalized::GeneralizedHilbert;
+#[cfg(feature = "geo")]
+pub use geo_support::{coord_from_index, index_coord};
+#[cfg(feature = "glam")]
+pub use glam_support::{from_hilbert_index_uvec2, from_hilbert_index_uvec3};
+pub use hierarchy::{children, coarsen, coarsen_point, level_offset, refine_point, split_level_offset};
+#[cfg(feature = "image")]
+pub use image_support::{hilbert_pixels, HilbertPixels};
+pub use index::HilbertIndex;
+pub use int::HilbertInt;
+#[cfg(feature = "alloc")]
+pub use iter::collect_points;
+pub use iter::{enumerate_points, for_each_point, indices, indices_range, next_point, next_point_toroidal, points, points_range, predecessors, prev_point, prev_point_toroidal, segments, successors, try_for_each_point, try_indices, turning_points, EnumeratePoints, Indices, Points, Predecessors, Segments, Successors, TurningPoints};
+#[cfg(feature = "rayon")]
+pub use iter::par_points;
+pub use layout::{hilbert_to_rowmajor, rowmajor_to_hilbert, HilbertLayout2D};
+pub use level::{axis_extent, checked_axis_extent, checked_num_cells, clamp_point, enclosing_compact_levels, enclosing_level, level_for, level_for_points, max_level, num_cells, to_hilbert_index_auto, to_hilbert_index_saturating};
+#[cfg(feature = "alloc")]
+pub use locality::compare_orderings;
+#[cfg(feature = "rayon")]
+pub use locality::validate_curve_parallel;
+pub use locality::{curve_distance, curve_interval, curve_jump, max_jump_over_range, validate_curve, validate_path, LocalityStats, OrderingStats};
+pub use masked::to_hilbert_index_masked;
+pub use moore::{from_moore_index, to_moore_index};
+pub use morton::{from_morton_index, hilbert_to_morton, morton_to_hilbert, to_morton_index};
+#[cfg(all(feature = "ndarray", feature = "alloc"))]
+pub use ndarray_support::hilbert_index_order;
+#[cfg(feature = "alloc")]
+pub use neighbors::{neighbors, neighbors_toroidal};
+pub use ord::HilbertOrd;
+pub use point::{Index, ParsePointError, Point};
+pub use position::HasGridPosition;
+pub use precomputed::HilbertTransform;
+#[cfg(feature = "alloc")]
+pub use query::{box_points, box_to_ranges, BoxPoints};
+pub use reversed::{from_hilbert_index_reversed, to_hilbert_index_reversed};
+pub use rtree::{largest_hilbert_value, mbr};
+pub use shard::{shard_of, shard_range};
+pub use signed::{FromCenteredHilbertIndex, ToCenteredHilbertIndex};
+#[cfg(feature = "alloc")]
+pub use sort::{hilbert_sort, hilbert_sort_by_key};
+pub use space::HilbertSpace;
+pub use state::{decode_with_state, encode_with_state};
+#[cfg(feature = "alloc")]
+pub use svg::{svg_path_from_points, to_svg_path};
+pub use tuple::{from_hilbert_index_tuple2, from_hilbert_index_tuple3};
+pub use unit::{from_unit_coords, nearest_index_unit, to_unit_coords};
+#[cfg(feature = "wasm")]
+pub use wasm::HilbertJs;
 
-// Gray code の逆変換.
-#[inline]
-fn gc_inv<const D: usize>(g: usize) -> usize { (1..D).fold(g, |i, j| i^(g>>j)) }
+use transform::{t, t_inv};
 
-#[inline]
-fn g(i: usize) -> usize {
-    (!i).trailing_zeros() as usize
+// 基本格子における部分格子の数 2^D - 1.
+// 全ての変換はこれを経由するので, D = 0 と D >= usize::BITS はここでコンパイルエラーにする.
+const fn max<const D: usize>() -> usize {
+    let () = Dim::<D>::CHECK;
+    dimension_mask::<D>()
+}
+
+struct Dim<const D: usize>;
+
+impl<const D: usize> Dim<D> {
+    const CHECK: () = {
+        assert!(D >= 1, "the dimension D must be at least 1");
+        assert!(D < usize::BITS as usize, "the dimension D must be less than usize::BITS");
+    };
 }
 
 #[inline]
-fn dmap<const D: usize>(i: usize) -> usize {
-    if i == 0 { 0 } else if i&1 == 0 { g(i-1) % D } else { g(i) % D }
+const fn dmap<const D: usize>(i: usize) -> usize {
+    if i == 0 { 0 } else if i&1 == 0 { gray::changed_bit(i-1) % D } else { gray::changed_bit(i) % D }
 }
 
 #[inline]
-fn emap(i: usize) -> usize {
-    if i == 0 { 0 } else { gc(2*( (i-1)/2 )) }
+const fn emap(i: usize) -> usize {
+    if i == 0 { 0 } else { gray::encode(2*( (i-1)/2 )) }
 }
 
 // #[inline]
 // #[allow(dead_code)]
 // fn fmap<const D: usize>(i: usize) -> usize { emap(i)^(1 << dmap::<D>(i)) }
 
-// D bit の範囲で右回転
 #[inline]
-fn rotate_right<const D: usize>(b: usize, i: usize) -> usize {
-    let i = i.rem_euclid(D);
-    (b >> i)^(b << (D-i))&max::<D>()
-}
+fn reduce<const D: usize>(p: &[usize; D], i: usize) -> usize { extract_bit_plane(p, i) }
 
-// D bit の範囲で左回転
+// level i で有効な次元 (m_k > i となる k) のビットが立ったマスク
 #[inline]
-fn rotate_left<const D: usize>(b: usize, i: usize) -> usize {
-    let i = i.rem_euclid(D);
-    max::<D>() & (b << i)^(b >> (D-i))
+fn active<const D: usize>(levels: &[usize; D], i: usize) -> usize {
+    levels.iter().enumerate()
+        .fold(0, |mu, (k, &m)| mu|( ((m > i) as usize) << k ))
 }
 
+// Gray code rank: mu のビットが立っている位置だけ w から取り出して詰める
 #[inline]
-fn t<const D: usize>(b: usize, e: usize, d: usize) -> usize { rotate_right::<D>(b^e, d+1) }
-
-#[inline]
-fn t_inv<const D: usize>(b: usize, e: usize, d: usize) -> usize { rotate_left::<D>(b, d+1)^e }
+fn gcr<const D: usize>(w: usize, mu: usize) -> usize {
+    if mu == max::<D>() { return w; }
+    (0..D).rev()
+        .filter(|k| (mu >> k)&1 == 1)
+        .fold(0, |r, k| (r << 1)|((w >> k)&1))
+}
 
+// Gray code rank の逆変換. 固定されたビット pi を補って (w, gc(w)) を返す.
 #[inline]
-fn reduce<const D: usize>(p: &[usize; D], i: usize) -> usize {
-    p.iter().enumerate()
-        .fold(0, |l, (k, p)| l^( ((p >> i)&1) << k))
+fn gcr_inv<const D: usize>(r: usize, mu: usize, pi: usize) -> (usize, usize) {
+    if mu == max::<D>() { return (r, gray::encode(r)); }
+    let (mut w, mut l) = (0, 0);
+    let mut j = mu.count_ones() as usize;
+    for k in (0..D).rev() {
+        let upper = (w >> (k+1))&1;
+        if (mu >> k)&1 == 1 {
+            j -= 1;
+            let bit = (r >> j)&1;
+            w |= bit << k;
+            l |= (bit^upper) << k;
+        } else {
+            let bit = (pi >> k)&1;
+            l |= bit << k;
+            w |= (bit^upper) << k;
+        }
+    }
+    (w, l)
 }
 
-// fn offset<const D: usize>(level: usize) -> usize {
-//     (0..level).fold(0, |ofs, _| {
-//         (ofs << D) | 1
-//     })
-// }
-
 /// Convert `[usize; D]` to `usize`.
 /// 
 /// # Usage
@@ -179,14 +339,185 @@ fn reduce<const D: usize>(p: &[usize; D], i: usize) -> usize {
 /// assert_eq!( 8, [2, 2].to_hilbert_index(level) );
 /// ```
 /// 
+/// The dimension `D` must satisfy `1 <= D < usize::BITS`, otherwise it does not compile:
+/// 
+/// ```compile_fail
+/// use hilbert_index::ToHilbertIndex;
+/// 
+/// let p: [usize; 0] = [];
+/// p.to_hilbert_index(1);
+/// ```
+/// 
+/// ```compile_fail
+/// use hilbert_index::ToHilbertIndex;
+/// 
+/// let p = [0; 128];
+/// p.to_hilbert_index(0);
+/// ```
+/// 
 pub trait ToHilbertIndex<const D: usize> {
+    /// The grid point as `[usize; D]`.
+    /// 
+    /// This is the only required method: all the conversions are provided on top of it,
+    /// so a custom point type only has to convert itself to an array.
+    /// 
+    /// ```
+    /// use hilbert_index::ToHilbertIndex;
+    /// 
+    /// struct Cell { x: u16, y: u16 }
+    /// 
+    /// impl ToHilbertIndex<2> for Cell {
+    ///     fn to_grid_point(&self) -> [usize; 2] { [self.x as usize, self.y as usize] }
+    /// }
+    /// 
+    /// assert_eq!( [1, 1].to_hilbert_index(1), Cell { x: 1, y: 1 }.to_hilbert_index(1) );
+    /// ```
+    /// 
+    fn to_grid_point(&self) -> [usize; D];
+
     /// Convert a grid point `[usize; D]` to a Hilbert index `usize`.
-    fn to_hilbert_index(&self, level: usize) -> usize;
+    /// 
+    /// The result is equal to `to_compact_hilbert_index([level; D])`,
+    /// but it is computed directly instead of going through the compact conversion, which is about 3 times slower.
+    /// For `D = 2, 3` several levels are processed at once with state transition tables.
+    fn to_hilbert_index(&self, level: usize) -> usize {
+        debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        let p = self.to_grid_point();
+        match D {
+            2 => lut::encode::<2, 4, 256>(&lut::ENCODE_2, &p, level),
+            3 => lut::encode::<3, 2, 64>(&lut::ENCODE_3, &p, level),
+            _ => to_words(&p, level, 0, 0),
+        }
+    }
 
     /// Equivalent to `to_hilbert_index` (abbreviation).
     fn to_hindex(&self, level: usize) -> usize {
         self.to_hilbert_index(level)
     }
+
+    /// Checked version of `to_hilbert_index`.
+    /// 
+    /// Returns `Err(HilbertError::LevelOutOfRange { .. })` if `level > max_level::<D>()`,
+    /// and `Err(HilbertError::OutOfRange { .. })` if some component `x` does not satisfy `x < 2.pow(level)`.
+    /// 
+    /// ```
+    /// use hilbert_index::{HilbertError, ToHilbertIndex};
+    /// 
+    /// assert_eq!( Ok(2), [1, 1].try_to_hilbert_index(1) );
+    /// assert_eq!(
+    ///     Err(HilbertError::OutOfRange { dim: 1, value: 2, bound: 2 }),
+    ///     [1, 2].try_to_hilbert_index(1),
+    /// );
+    /// ```
+    /// 
+    fn try_to_hilbert_index(&self, level: usize) -> Result<usize, HilbertError> {
+        level::check_level::<D>(level)?;
+        let p = self.to_grid_point();
+        for (dim, &value) in p.iter().enumerate() {
+            if value.checked_shr(level as u32).unwrap_or(0) > 0 {
+                return Err(HilbertError::OutOfRange { dim, value, bound: axis_extent(level) });
+            }
+        }
+
+        Ok(p.to_hilbert_index(level))
+    }
+
+    /// Convert a grid point `[usize; D]` to a Hilbert index `usize` along the curve
+    /// which starts with the entry vertex `e0` and the direction `d0` (`e0 < 2.pow(D)`, `d0 < D`).
+    /// 
+    /// The curve starts at the corner of the box whose `k`-th component is `2.pow(level) - 1`
+    /// if the `k`-th bit of `e0` is set and `0` otherwise, and at the coarsest level
+    /// it first moves along the axis `(d0 + 1)%D`. Curves with different seeds are reflections and rotations of each other,
+    /// so they can be stitched across sub-blocks. `(e0, d0) = (0, 0)` gives `to_hilbert_index`.
+    /// The inverse is `from_hilbert_index_with`.
+    /// 
+    /// ```
+    /// use hilbert_index::ToHilbertIndex;
+    /// 
+    /// let level = 2;
+    /// assert_eq!( [2, 1].to_hilbert_index(level), [2, 1].to_hilbert_index_with(level, 0, 0) );
+    /// assert_eq!( 0, [3, 0].to_hilbert_index_with(level, 0b01, 0) );
+    /// ```
+    /// 
+    fn to_hilbert_index_with(&self, level: usize, e0: usize, d0: usize) -> usize {
+        debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        to_words(&self.to_grid_point(), level, e0, d0)
+    }
+
+    /// Convert a grid point `[usize; D]` to a compact Hilbert index `usize`,
+    /// where the `k`-th component satisfies `0 <= x < 2.pow(levels[k])`.
+    /// 
+    /// The result lies in `0..2.pow(levels.iter().sum())`, so a box with unequal side lengths
+    /// is indexed without wasted bits. With `levels == [level; D]` it is equal to `to_hilbert_index(level)`.
+    /// 
+    /// ```
+    /// use hilbert_index::ToHilbertIndex;
+    /// 
+    /// let levels = [2, 1];
+    /// assert_eq!( 0, [0, 0].to_compact_hilbert_index(levels) );
+    /// assert_eq!( 7, [3, 0].to_compact_hilbert_index(levels) );
+    /// ```
+    /// 
+    fn to_compact_hilbert_index(&self, levels: [usize; D]) -> usize {
+        debug_assert!(levels.iter().sum::<usize>() < 8*core::mem::size_of::<usize>(), "levels {:?} do not fit in usize", levels);
+        let p = self.to_grid_point();
+        let (mut h, mut e, mut d) = (0, 0, 0);
+        for i in (0..levels.iter().copied().max().unwrap_or(0)).rev() {
+            let mu = gray::rotate_right::<D>(active(&levels, i), d+1);
+            let l = t::<D>(reduce(&p, i), e, d);
+            let w = gray::decode::<D>(l);
+            e ^= gray::rotate_left::<D>(emap(w), d+1);
+            d = ( d + dmap::<D>(w) + 1 )%D;
+            h = (h << mu.count_ones()) | gcr::<D>(w, mu);
+        }
+
+        h
+    }
+
+    /// Convert a grid point `[usize; D]` to a Hilbert index `u64`.
+    /// 
+    /// The index is computed with 64-bit arithmetic regardless of the target,
+    /// so the result is identical on 32-bit and 64-bit platforms as long as `D*level <= 64`.
+    /// The inverse is `from_hilbert_index_u64`.
+    /// 
+    /// ```
+    /// use hilbert_index::{from_hilbert_index_u64, ToHilbertIndex};
+    /// 
+    /// let level = 20;
+    /// let hindex: u64 = [1 << 19, 0, 0].to_hilbert_index_u64(level);
+    /// assert_eq!( [1 << 19, 0, 0], from_hilbert_index_u64(hindex, level) );
+    /// ```
+    /// 
+    fn to_hilbert_index_u64(&self, level: usize) -> u64 {
+        self.to_hilbert_index_as::<u64>(level)
+    }
+
+    /// Convert a grid point `[usize; D]` to a Hilbert index of an integer type `T`,
+    /// which must satisfy `D*level <= T::BITS`.
+    /// The inverse is `from_hilbert_index_as`.
+    /// 
+    /// ```
+    /// use hilbert_index::ToHilbertIndex;
+    /// 
+    /// let hindex: u16 = [1, 1].to_hilbert_index_as(1);
+    /// assert_eq!( 2, hindex );
+    /// ```
+    /// 
+    fn to_hilbert_index_as<T: HilbertInt>(&self, level: usize) -> T {
+        to_words(&self.to_grid_point(), level, 0, 0)
+    }
+
+    /// Convert a grid point `[usize; D]` to a Hilbert index `BigUint` of arbitrary size
+    /// (requires the `num-bigint` feature).
+    /// 
+    /// This is useful when `D*level` exceeds 128 bits, e.g. `D = 10` and `level = 30`.
+    /// The per-level work is the same as the primitive path, but each level allocates,
+    /// so it is considerably slower than `to_hilbert_index` or `to_hilbert_index_as::<u128>`.
+    /// The inverse is `from_hilbert_index_big`.
+    #[cfg(feature = "num-bigint")]
+    fn to_hilbert_index_big(&self, level: usize) -> num_bigint::BigUint {
+        self.to_hilbert_index_as(level)
+    }
 }
 
 /// Convert `usize` to `[usize; D]`.
@@ -210,43 +541,114 @@ pub trait ToHilbertIndex<const D: usize> {
 /// assert_eq!( [2, 2], 8.from_hilbert_index(level) );
 /// ```
 /// 
+#[allow(clippy::wrong_self_convention)]
 pub trait FromHilbertIndex<const D: usize> {
+    /// The Hilbert index as `usize`.
+    /// 
+    /// This is the only required method: all the conversions are provided on top of it.
+    fn as_hilbert_index(&self) -> usize;
+
     /// Convert a Hilbert index `usize` to a grid point `[usize; D]`.
-    fn from_hilbert_index(&self, level: usize) -> [usize; D];
+    /// 
+    /// The result is equal to `from_compact_hilbert_index([level; D])`,
+    /// but it is computed directly instead of going through the compact conversion (see `from_hilbert_index_into`).
+    fn from_hilbert_index(&self, level: usize) -> [usize; D] {
+        let mut p = [0; D];
+        from_hilbert_index_into(self.as_hilbert_index(), level, &mut p);
+        p
+    }
 
     /// Equivalent to `from_hilbert_index` (abbreviation).
     fn from_hindex(&self, level: usize) -> [usize; D] {
         self.from_hilbert_index(level)
     }
-}
 
-impl<const D: usize> ToHilbertIndex::<D> for [usize; D] {
-    fn to_hilbert_index(&self, level: usize) -> usize {
-        let (mut h, mut e, mut d) = (0, 0, 0);
-        for i in(0..level).rev() {
-            let l = t::<D>(reduce(&self, i), e, d);
-            let w = gc_inv::<D>(l);
-            e = e^( rotate_left::<D>(emap(w), d+1) );
-            d = ( d + dmap::<D>(w) + 1 )%D;
-            h = (h << D) | w;
+    /// Convert a Hilbert index `usize` to a grid point `[C; D]` of a custom coordinate type (see `Coordinate`).
+    /// 
+    /// ```
+    /// use hilbert_index::FromHilbertIndex;
+    /// 
+    /// let p: [usize; 2] = 2.from_hilbert_index_coords(1);
+    /// assert_eq!( [1, 1], p );
+    /// ```
+    /// 
+    fn from_hilbert_index_coords<C: Coordinate>(&self, level: usize) -> [C; D] {
+        self.from_hilbert_index(level).map(C::from_grid)
+    }
+
+    /// Checked version of `from_hilbert_index`.
+    /// 
+    /// Returns `Err(HilbertError::LevelOutOfRange { .. })` if `level > max_level::<D>()`,
+    /// and `Err(HilbertError::IndexOutOfRange { .. })` if the index does not satisfy `h < 2.pow(D*level)`.
+    /// 
+    /// ```
+    /// use hilbert_index::{FromHilbertIndex, HilbertError};
+    /// 
+    /// assert_eq!( Ok([1, 1]), 2.try_from_hilbert_index(1) );
+    /// assert_eq!(
+    ///     Err(HilbertError::IndexOutOfRange { index: 4, bound: 4 }),
+    ///     FromHilbertIndex::<2>::try_from_hilbert_index(&4, 1),
+    /// );
+    /// ```
+    /// 
+    fn try_from_hilbert_index(&self, level: usize) -> Result<[usize; D], HilbertError> {
+        level::check_level::<D>(level)?;
+        let h = self.as_hilbert_index();
+        if h >> (D*level) > 0 {
+            return Err(HilbertError::IndexOutOfRange { index: h, bound: num_cells::<D>(level) });
         }
 
-        h
+        Ok(h.from_hilbert_index(level))
     }
-}
 
-impl<const D: usize> FromHilbertIndex::<D> for usize {
-    fn from_hilbert_index(&self, level: usize) -> [usize; D] {
+    /// Convert a Hilbert index `usize` to a grid point `[usize; D]` along the curve
+    /// which starts with the entry vertex `e0` and the direction `d0`.
+    /// This is the inverse of `ToHilbertIndex::to_hilbert_index_with`.
+    /// 
+    /// ```
+    /// use hilbert_index::FromHilbertIndex;
+    /// 
+    /// let level = 2;
+    /// assert_eq!( [3, 0], 0.from_hilbert_index_with(level, 0b01, 0) );
+    /// assert_eq!( [2, 0], 1.from_hilbert_index_with(level, 0b01, 0) );
+    /// ```
+    /// 
+    fn from_hilbert_index_with(&self, level: usize, e0: usize, d0: usize) -> [usize; D] {
+        debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        from_words(&self.as_hilbert_index(), level, e0, d0)
+    }
+
+    /// Convert a compact Hilbert index `usize` to a grid point `[usize; D]`,
+    /// where the `k`-th component has `levels[k]` bits.
+    /// This is the inverse of `ToHilbertIndex::to_compact_hilbert_index`.
+    /// 
+    /// ```
+    /// use hilbert_index::FromHilbertIndex;
+    /// 
+    /// let levels = [2, 1];
+    /// assert_eq!( [0, 0], 0.from_compact_hilbert_index(levels) );
+    /// assert_eq!( [3, 0], 7.from_compact_hilbert_index(levels) );
+    /// ```
+    /// 
+    fn from_compact_hilbert_index(&self, levels: [usize; D]) -> [usize; D] {
+        let h = self.as_hilbert_index();
         let (mut e, mut d) = (0, 0);
+        let mut k: usize = levels.iter().sum();
+        debug_assert!(k < 8*core::mem::size_of::<usize>(), "levels {:?} do not fit in usize", levels);
         let mut p = [0; D];
 
-        for i in (0..level).rev() {
-            let w = (0..D).fold(0, |w, k| w^( ((self >> (i*D + k)) & 1 ) << k ));
-            let l = t_inv::<D>(gc(w), e, d);
-            for j in 0..D {
-                p[j] = (p[j] << 1)|((l >> j)&1);
+        for i in (0..levels.iter().copied().max().unwrap_or(0)).rev() {
+            let mu = gray::rotate_right::<D>(active(&levels, i), d+1);
+            let pi = gray::rotate_right::<D>(e, d+1) & !mu;
+            let b = mu.count_ones() as usize;
+            k -= b;
+            let r = (h >> k) & !( {usize::MAX}<<b );
+            let (w, l) = gcr_inv::<D>(r, mu, pi);
+            let l = t_inv::<D>(l, e, d);
+            for (j, x) in p.iter_mut().enumerate() {
+                *x = (*x << 1)|((l >> j)&1);
             }
-            e = e^rotate_left::<D>( emap(w), d+1 );
+            e ^= gray::rotate_left::<D>( emap(w), d+1 );
             d = ( d + dmap::<D>(w) + 1 )%D;
         }
 
@@ -254,9 +656,130 @@ impl<const D: usize> FromHilbertIndex::<D> for usize {
     }
 }
 
+impl<C: Coordinate, const D: usize> ToHilbertIndex::<D> for [C; D] {
+    #[inline]
+    fn to_grid_point(&self) -> [usize; D] {
+        coordinate::to_grid(self)
+    }
+}
+
+fn to_words<T: HilbertInt, const D: usize>(p: &[usize; D], level: usize, e0: usize, d0: usize) -> T {
+    to_words_state(p, level, e0, d0).0
+}
+
+// to_words に加えて, 最後の level を処理した後の状態 (e, d) を返す
+fn to_words_state<T: HilbertInt, const D: usize>(p: &[usize; D], level: usize, e0: usize, d0: usize) -> (T, usize, usize) {
+    debug_assert!(D*level <= T::BITS, "D*level = {} exceeds {} bits", D*level, T::BITS);
+    debug_assert!(e0 <= max::<D>() && d0 < D, "invalid seed (e0, d0) = ({}, {})", e0, d0);
+    let (mut h, mut e, mut d) = (T::zero(), e0, d0);
+    for i in (0..level).rev() {
+        let l = t::<D>(reduce(p, i), e, d);
+        let w = gray::decode::<D>(l);
+        e ^= gray::rotate_left::<D>(emap(w), d+1);
+        d = ( d + dmap::<D>(w) + 1 )%D;
+        h = h.push_word(w, D);
+    }
+
+    (h, e, d)
+}
+
+impl<const D: usize> FromHilbertIndex::<D> for usize {
+    #[inline]
+    fn as_hilbert_index(&self) -> usize { *self }
+}
+
+/// Convert a Hilbert index `usize` to a grid point, written into `out`.
+/// 
+/// This is the same as `FromHilbertIndex::from_hilbert_index`, which calls this function,
+/// but avoids moving the resulting array in hot loops with large `D`.
+/// The previous content of `out` is overwritten.
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_into, FromHilbertIndex};
+/// 
+/// let level = 3;
+/// let mut p = [0; 6];
+/// from_hilbert_index_into(100, level, &mut p);
+/// assert_eq!( p, 100.from_hilbert_index(level) );
+/// ```
+/// 
+pub fn from_hilbert_index_into<const D: usize>(h: usize, level: usize, out: &mut [usize; D]) {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    out.iter_mut().for_each(|x| *x = 0);
+    match D {
+        2 => lut::decode::<2, 4, 256>(&lut::DECODE_2, h, level, out),
+        3 => lut::decode::<3, 2, 64>(&lut::DECODE_3, h, level, out),
+        _ => { from_words_into(&h, level, 0, 0, out); },
+    }
+}
+
+/// Convert a Hilbert index `u64` to a grid point `[usize; D]`.
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index_u64`,
+/// and gives the same result on 32-bit and 64-bit platforms.
+pub fn from_hilbert_index_u64<const D: usize>(h: u64, level: usize) -> [usize; D] {
+    from_hilbert_index_as(h, level)
+}
+
+/// Convert a Hilbert index of an integer type `T` to a grid point `[usize; D]`.
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index_as`.
+/// 
+/// ```
+/// use hilbert_index::from_hilbert_index_as;
+/// 
+/// assert_eq!( [1, 1], from_hilbert_index_as(2u16, 1) );
+/// ```
+/// 
+pub fn from_hilbert_index_as<T: HilbertInt, const D: usize>(h: T, level: usize) -> [usize; D] {
+    from_words(&h, level, 0, 0)
+}
+
+/// Convert a Hilbert index `BigUint` to a grid point `[usize; D]`
+/// (requires the `num-bigint` feature).
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index_big`.
+/// It is considerably slower than the primitive path; see `to_hilbert_index_big`.
+#[cfg(feature = "num-bigint")]
+pub fn from_hilbert_index_big<const D: usize>(h: &num_bigint::BigUint, level: usize) -> [usize; D] {
+    from_words(h, level, 0, 0)
+}
+
+fn from_words<T: HilbertInt, const D: usize>(h: &T, level: usize, e0: usize, d0: usize) -> [usize; D] {
+    from_words_state(h, level, e0, d0).0
+}
+
+// from_words に加えて, 最後の level を処理した後の状態 (e, d) を返す
+fn from_words_state<T: HilbertInt, const D: usize>(h: &T, level: usize, e0: usize, d0: usize) -> ([usize; D], usize, usize) {
+    let mut p = [0; D];
+    let (e, d) = from_words_into(h, level, e0, d0, &mut p);
+    (p, e, d)
+}
+
+// 格子点を p に書き込み, 最後の level を処理した後の状態 (e, d) を返す
+fn from_words_into<T: HilbertInt, const D: usize>(h: &T, level: usize, e0: usize, d0: usize, p: &mut [usize; D]) -> (usize, usize) {
+    debug_assert!(D*level <= T::BITS, "D*level = {} exceeds {} bits", D*level, T::BITS);
+    debug_assert!(e0 <= max::<D>() && d0 < D, "invalid seed (e0, d0) = ({}, {})", e0, d0);
+    let (mut e, mut d) = (e0, d0);
+    p.iter_mut().for_each(|x| *x = 0);
+
+    for i in (0..level).rev() {
+        let w = h.word(i*D, D);
+        let l = t_inv::<D>(gray::encode(w), e, d);
+        for (j, x) in p.iter_mut().enumerate() {
+            *x = (*x << 1)|((l >> j)&1);
+        }
+        e ^= gray::rotate_left::<D>( emap(w), d+1 );
+        d = ( d + dmap::<D>(w) + 1 )%D;
+    }
+
+    (e, d)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{FromHilbertIndex, ToHilbertIndex};
+    use proptest::prelude::*;
+    use crate::{from_hilbert_index_into, from_hilbert_index_u64, FromHilbertIndex, HilbertError, ToHilbertIndex};
 
     fn check<const D: usize>(level: usize) {
         //let max: usize = !( {std::usize::MAX}<<D );
@@ -286,6 +809,91 @@ mod tests {
         }
     }
 
+    // level 0 では格子点 [0; D] と添字 0 だけがある
+    fn check_level_zero<const D: usize>() {
+        let p = [0; D];
+        assert_eq!(0, p.to_hilbert_index(0));
+        assert_eq!(0, p.to_compact_hilbert_index([0; D]));
+        assert_eq!(0, p.to_hilbert_index_as::<u16>(0));
+        assert_eq!(Ok(0), p.try_to_hilbert_index(0));
+        assert_eq!(p, 0.from_hilbert_index(0));
+        assert_eq!(p, 0.from_compact_hilbert_index([0; D]));
+        assert_eq!(p, from_hilbert_index_u64(0, 0));
+        assert_eq!(Ok(p), 0.try_from_hilbert_index(0));
+        assert!(FromHilbertIndex::<D>::try_from_hilbert_index(&1, 0).is_err());
+        assert!([1; D].try_to_hilbert_index(0).is_err());
+
+        assert_eq!(vec![p], crate::points::<D>(0).collect::<Vec<_>>());
+        assert_eq!(vec![0], crate::indices::<D>(0).collect::<Vec<_>>());
+        assert_eq!(None, crate::next_point(&p, 0));
+        assert_eq!(p, crate::HilbertTransform::<D>::new(0).decode(0));
+        assert_eq!(0, crate::to_hilbert_index_const(p, 0));
+        #[cfg(feature = "alloc")]
+        assert_eq!(vec![0..1], crate::box_to_ranges(p, p, 0));
+        assert_eq!(0, crate::DynHilbert::new(D, 0).to_index(&p));
+    }
+
+    #[test]
+    fn level_zero() {
+        check_level_zero::<1>();
+        check_level_zero::<2>();
+        check_level_zero::<3>();
+        check_level_zero::<5>();
+    }
+
+    // usize::BITS 未満の次元は変換できる
+    #[test]
+    fn large_dimension() {
+        const D: usize = usize::BITS as usize - 1;
+        check_level_zero::<D>();
+        assert_eq!(crate::max_level::<D>(), 1);
+        for &h in [0, 1, 2, 12345, (1 << D) - 1].iter() {
+            let p: [usize; D] = h.from_hilbert_index(1);
+            assert_eq!(p.to_hilbert_index(1), h);
+        }
+    }
+
+    #[test]
+    fn decode_into() {
+        fn check<const D: usize>(level: usize) {
+            // 以前の内容は上書きされる
+            let mut out = [usize::MAX; D];
+            for h in crate::indices::<D>(level) {
+                from_hilbert_index_into(h, level, &mut out);
+                assert_eq!(out, h.from_hilbert_index(level));
+            }
+        }
+        check::<6>(3);
+        check::<2>(4);
+        check::<3>(3);
+        check::<1>(5);
+    }
+
+    // 1 次元の曲線は恒等写像になる
+    #[test]
+    fn dim_one() {
+        const D: usize = 1;
+        for level in 1..10 { check::<D>(level); }
+
+        for level in [0, 1, 5, 32, crate::max_level::<D>()].iter().copied() {
+            let max = !( {usize::MAX}.checked_shl(level as u32).unwrap_or(0) );
+            for x in [0, 1, max/3, max.saturating_sub(1), max].iter().copied().filter(|&x| x <= max) {
+                assert_eq!(x, [x].to_hilbert_index(level));
+                assert_eq!(x, [x].to_compact_hilbert_index([level]));
+                assert_eq!([x], x.from_hilbert_index(level));
+                assert_eq!([x], x.from_compact_hilbert_index([level]));
+                assert_eq!(x as u64, [x].to_hilbert_index_u64(level));
+            }
+        }
+
+        for e in 0..2 {
+            for i in 0..3 {
+                assert_eq!(crate::gray::rotate_right::<1>(e, i), e);
+                assert_eq!(crate::gray::rotate_left::<1>(e, i), e);
+            }
+        }
+    }
+
     #[test]
     fn dim_two() {
         const D: usize = 2;
@@ -315,4 +923,248 @@ mod tests {
         const D: usize = 6;
         for level in 1..4 { check::<D>(level); }
     }
+
+    fn check_compact<const D: usize>(levels: [usize; D]) {
+        let n = 2usize.pow(levels.iter().sum::<usize>() as u32);
+        let mut visited = vec![false; n];
+
+        for key in 0..n {
+            let xyz: [usize; D] = key.from_compact_hilbert_index(levels);
+            assert_eq!(key, xyz.to_compact_hilbert_index(levels));
+
+            // 各成分が箱の中に入っていることをチェック
+            for (x, &m) in xyz.iter().zip(levels.iter()) {
+                assert!(*x < 2usize.pow(m as u32));
+            }
+
+            // 箱の中の点はちょうど 1 回ずつ現れる
+            let flat = xyz.iter().zip(levels.iter())
+                .fold(0, |f, (&x, &m)| (f << m) | x);
+            assert!(!visited[flat]);
+            visited[flat] = true;
+        }
+    }
+
+    #[test]
+    fn compact_anisotropic() {
+        check_compact([3, 1]);
+        check_compact([0, 4]);
+        check_compact([2, 5]);
+        check_compact([3, 1, 2]);
+        check_compact([0, 2, 4]);
+        check_compact([1, 3, 2, 2]);
+        check_compact([4, 0, 1, 2, 2]);
+    }
+
+    #[test]
+    fn compact_equal_levels() {
+        const D: usize = 3;
+        let level = 3;
+        for key in 0..2usize.pow((D*level) as u32) {
+            let xyz: [usize; D] = key.from_compact_hilbert_index([level; D]);
+            assert_eq!(xyz, key.from_hilbert_index(level));
+            assert_eq!(key, xyz.to_compact_hilbert_index([level; D]));
+        }
+    }
+
+    #[test]
+    fn checked() {
+        const D: usize = 3;
+        let level = 2;
+        for key in 0..2usize.pow((D*level) as u32) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(Ok(xyz), key.try_from_hilbert_index(level));
+            assert_eq!(Ok(key), xyz.try_to_hilbert_index(level));
+        }
+
+        assert_eq!(
+            Err(HilbertError::OutOfRange { dim: 2, value: 4, bound: 4 }),
+            [0, 3, 4].try_to_hilbert_index(level),
+        );
+        assert_eq!(
+            Err(HilbertError::IndexOutOfRange { index: 64, bound: 64 }),
+            FromHilbertIndex::<D>::try_from_hilbert_index(&64, level),
+        );
+    }
+
+    fn check_level<const D: usize>() {
+        let level = crate::max_level::<D>();
+        let err = HilbertError::LevelOutOfRange { level: level + 1, max_level: level };
+        assert_eq!(Some(&err), [1; D].try_to_hilbert_index(level + 1).err().as_ref());
+        assert_eq!(Some(&err), [0; D].try_to_hilbert_index(level + 1).err().as_ref());
+        assert_eq!(Some(&err), FromHilbertIndex::<D>::try_from_hilbert_index(&5, level + 1).err().as_ref());
+        assert_eq!(Some(&err), FromHilbertIndex::<D>::try_from_hilbert_index(&0, level + 1).err().as_ref());
+
+        // max_level ちょうどでは全ての添字が扱える
+        let last = usize::MAX >> (usize::BITS as usize - D*level);
+        let p: [usize; D] = last.from_hilbert_index(level);
+        assert_eq!(Ok(p), last.try_from_hilbert_index(level));
+        assert_eq!(Ok(last), p.try_to_hilbert_index(level));
+        assert_eq!(
+            Err(HilbertError::IndexOutOfRange { index: last + 1, bound: last + 1 }),
+            FromHilbertIndex::<D>::try_from_hilbert_index(&(last + 1), level),
+        );
+    }
+
+    #[test]
+    fn checked_level() {
+        check_level::<1>();
+        check_level::<2>();
+        check_level::<3>();
+        check_level::<5>();
+        check_level::<8>();
+    }
+
+    fn check_seed<const D: usize>(level: usize, e0: usize, d0: usize) {
+        let n = 2usize.pow((D*level) as u32);
+        let mut visited = vec![false; n];
+        let mut prv: Option<[usize; D]> = None;
+
+        for key in 0..n {
+            let xyz: [usize; D] = key.from_hilbert_index_with(level, e0, d0);
+            assert_eq!(key, xyz.to_hilbert_index_with(level, e0, d0));
+
+            // 格子点はちょうど 1 回ずつ現れる
+            let flat = xyz.iter().fold(0, |f, &x| (f << level) | x);
+            assert!(!visited[flat]);
+            visited[flat] = true;
+
+            if let Some(prv) = prv {
+                let diff = prv.iter().zip(xyz.iter())
+                    .map(|(&p, &c)| (p as isize - c as isize).abs())
+                    .sum::<isize>();
+                assert_eq!(diff, 1);
+            }
+            prv = Some(xyz);
+        }
+
+        // 曲線は e0 が表す頂点から始まる
+        let start: [usize; D] = 0.from_hilbert_index_with(level, e0, d0);
+        for (k, &x) in start.iter().enumerate() {
+            assert_eq!(x, if (e0 >> k)&1 == 1 { (1 << level) - 1 } else { 0 });
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        const D: usize = 3;
+        let level = 3;
+        for key in 0..2usize.pow((D*level) as u32) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(xyz, key.from_hilbert_index_with(level, 0, 0));
+            assert_eq!(key, xyz.to_hilbert_index_with(level, 0, 0));
+        }
+
+        for e0 in 0..1 << 2 {
+            for d0 in 0..2 { check_seed::<2>(4, e0, d0); }
+        }
+        for e0 in 0..1 << 3 {
+            for d0 in 0..3 { check_seed::<3>(2, e0, d0); }
+        }
+        check_seed::<4>(2, 0b1010, 3);
+    }
+
+    // 添字 h と h+1 について逆写像と隣接性をチェックする
+    fn roundtrip<const D: usize>(level: usize, h: usize) {
+        let p: [usize; D] = h.from_hilbert_index(level);
+        assert_eq!(h, p.to_hilbert_index(level));
+        assert_eq!(p, from_hilbert_index_u64(h as u64, level));
+
+        let q: [usize; D] = (h + 1).from_hilbert_index(level);
+        assert_eq!(h + 1, q.to_hilbert_index(level));
+        let changed = p.iter().zip(q.iter())
+            .filter(|(a, b)| a != b)
+            .map(|(&a, &b)| (a as isize - b as isize).abs())
+            .collect::<Vec<_>>();
+        assert_eq!(changed, vec![1]);
+    }
+
+    // 1 <= level <= max_level::<D>() と, h+1 も曲線上にある添字 h
+    fn level_and_index<const D: usize>() -> impl Strategy<Value = (usize, usize)> {
+        (1..=crate::max_level::<D>())
+            .prop_flat_map(|level| (Just(level), 0..(1usize << (D*level)) - 1))
+    }
+
+    macro_rules! proptest_roundtrip {
+        ($($name:ident: $d:expr,)*) => {
+            proptest! {
+                #![proptest_config(ProptestConfig::with_cases(64))]
+                $(
+                    #[test]
+                    fn $name((level, h) in level_and_index::<$d>()) {
+                        roundtrip::<$d>(level, h);
+                    }
+                )*
+            }
+        };
+    }
+
+    proptest_roundtrip! {
+        random_dim_two: 2,
+        random_dim_three: 3,
+        random_dim_four: 4,
+        random_dim_five: 5,
+        random_dim_six: 6,
+        random_dim_seven: 7,
+        random_dim_eight: 8,
+    }
+
+    // 64-bit の環境で計算した値. 32-bit の環境でも同じ値になる必要がある.
+    const FIXED_3D: [([usize; 3], u64); 3] = [
+        ([123456, 654321, 1], 145473461105037316),
+        ([0, 0, 524288], 479240188918917542),
+        ([1048575, 77, 1024], 1152921501036490778),
+    ];
+    const FIXED_2D: [([usize; 2], u64); 2] = [
+        ([4294967295, 305419896], 18182151845961777215),
+        ([1, 2147483648], 4611686018427387907),
+    ];
+
+    #[test]
+    fn u64_fixed_values() {
+        for &(p, h) in FIXED_3D.iter() {
+            assert_eq!(h, p.to_hilbert_index_u64(20));
+            assert_eq!(p, from_hilbert_index_u64(h, 20));
+        }
+        for &(p, h) in FIXED_2D.iter() {
+            assert_eq!(h, p.to_hilbert_index_u64(32));
+            assert_eq!(p, from_hilbert_index_u64(h, 32));
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn u64_same_as_usize() {
+        for &(p, h) in FIXED_3D.iter() {
+            assert_eq!(h as usize, p.to_hilbert_index(20));
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "32")]
+    fn u64_beyond_usize() {
+        // usize では level 10 までしか扱えないが, u64 なら level 20 まで扱える
+        assert_eq!(crate::max_level::<3>(), 10);
+        for &(p, h) in FIXED_3D.iter() {
+            assert!(h > usize::MAX as u64);
+            assert_eq!(p, from_hilbert_index_u64(h, 20));
+        }
+    }
+
+    // core だけで使える API (配列との変換, points) が alloc なしで動くことを確認する
+    #[test]
+    fn core_only() {
+        use core::iter::Iterator;
+
+        const D: usize = 3;
+        let level = 2;
+        let mut n = 0;
+        for (key, xyz) in crate::points::<D>(level).enumerate() {
+            assert_eq!(key, xyz.to_hilbert_index(level));
+            assert_eq!(xyz, FromHilbertIndex::<D>::from_hilbert_index(&key, level));
+            n += 1;
+        }
+        assert_eq!(n, 1 << (D*level));
+        assert!(D*level < usize::BITS as usize);
+    }
 }
@@ -0,0 +1,475 @@
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+use crate::iter::{last, Walker};
+use core::ops::Range;
+use crate::{max_level, points, FromHilbertIndex, ToHilbertIndex};
+#[cfg(feature = "alloc")]
+use crate::num_cells;
+
+/// The Manhattan distance between the grid points of the Hilbert indices `h` and `h+1`.
+/// 
+/// For a valid Hilbert curve this is always `1`, which is a testable form of its locality.
+/// Returns `0` if `h+1` is out of the curve, i.e. `h + 1 >= 2.pow(D*level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::curve_jump;
+/// 
+/// let level = 3;
+/// assert_eq!( 1, curve_jump::<2>(0, level) );
+/// assert_eq!( 1, curve_jump::<2>(62, level) );
+/// assert_eq!( 0, curve_jump::<2>(63, level) );
+/// ```
+/// 
+pub fn curve_jump<const D: usize>(h: usize, level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    if h >= last::<D>(level) { return 0; }
+    let p: [usize; D] = h.from_hilbert_index(level);
+    let q: [usize; D] = (h + 1).from_hilbert_index(level);
+    manhattan(&p, &q)
+}
+
+/// The largest `curve_jump::<D>(h, level)` for `h` in `start..end`.
+/// 
+/// Returns `0` for an empty range. The grid points are computed incrementally as in `points`,
+/// so this costs `O(D)` per index on average. It is mainly a diagnostic tool:
+/// `max_jump_over_range::<D>(0, n, level) == 1` means the whole curve preserves adjacency.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::max_jump_over_range;
+/// 
+/// let level = 4;
+/// assert_eq!( 1, max_jump_over_range::<3>(0, 1 << (3*level), level) );
+/// assert_eq!( 0, max_jump_over_range::<3>(5, 5, level) );
+/// ```
+/// 
+pub fn max_jump_over_range<const D: usize>(start: usize, end: usize, level: usize) -> usize {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    let end = end.min(last::<D>(level));
+    if start >= end { return 0; }
+
+    let mut walker = Walker::<D>::new(start, level);
+    let mut jump = 0;
+    for h in start..end {
+        let p = walker.p;
+        walker.seek(h + 1);
+        jump = jump.max(manhattan(&p, &walker.p));
+    }
+
+    jump
+}
+
+/// Check that consecutive grid points of the whole curve are adjacent.
+/// 
+/// Returns `Err((i, i+1))` for the first pair of consecutive indices whose grid points are not adjacent
+/// (i.e. their Manhattan distance is not `1`), or `Ok(())` if the whole curve is valid.
+/// This is a diagnostic for unusual levels and dimensions; see `validate_path` for other variants of the curve.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::validate_curve;
+/// 
+/// assert_eq!( Ok(()), validate_curve::<3>(3) );
+/// ```
+/// 
+pub fn validate_curve<const D: usize>(level: usize) -> Result<(), (usize, usize)> {
+    validate_path(points::<D>(level))
+}
+
+/// Parallel version of `validate_curve`, which also checks the round trip of every index (requires the `rayon` feature).
+/// 
+/// The curve is split into shards processed by `rayon`.
+/// Each shard reconstructs the state of the curve at its first index, as `par_points` does,
+/// then walks the shard checking that the grid point of every index `h` is encoded back to `h`,
+/// and that it is adjacent to the grid point of `h+1`, including the one in the next shard.
+/// Hence this also tests the state reconstruction used by `par_points`.
+/// 
+/// Returns `Err((h, h))` for the first index `h` whose grid point is not encoded back to `h`,
+/// `Err((h, h+1))` for the first pair of consecutive indices whose grid points are not adjacent,
+/// whichever is the smaller, or `Ok(())` if the whole curve is valid.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::validate_curve_parallel;
+/// 
+/// assert_eq!( Ok(()), validate_curve_parallel::<3>(4) );
+/// ```
+/// 
+#[cfg(feature = "rayon")]
+pub fn validate_curve_parallel<const D: usize>(level: usize) -> Result<(), (usize, usize)> {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    validate_shards::<D>(level, 0..num_shards::<D>(level))
+}
+
+// validate_curve_parallel で 1 つの shard が調べる添字の数
+#[cfg(feature = "rayon")]
+const SHARD: usize = 1 << 14;
+
+#[cfg(feature = "rayon")]
+fn num_shards<const D: usize>(level: usize) -> usize {
+    last::<D>(level)/SHARD + 1
+}
+
+// 与えられた shard だけを調べる validate_curve_parallel
+#[cfg(feature = "rayon")]
+fn validate_shards<const D: usize>(level: usize, shards: impl rayon::iter::IntoParallelIterator<Item = usize>) -> Result<(), (usize, usize)> {
+    use rayon::iter::ParallelIterator;
+
+    let end = last::<D>(level);
+    let first_error = shards.into_par_iter()
+        .filter_map(|s| {
+            // 各 shard は start..=stop の添字を調べる. 境界をまたぐ隣接も調べるため stop は次の shard の先頭と重なる.
+            let (start, stop) = (s*SHARD, ((s + 1)*SHARD).min(end));
+            let mut walker = Walker::<D>::new(start, level);
+            for h in start..=stop {
+                if walker.p.to_hilbert_index(level) != h || walker.p != h.from_hilbert_index(level) {
+                    return Some((h, h));
+                }
+                if h == stop { break; }
+                let p = walker.p;
+                walker.seek(h + 1);
+                if manhattan(&p, &walker.p) != 1 {
+                    return Some((h, h + 1));
+                }
+            }
+            None
+        })
+        .min();
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Check that consecutive grid points of a path are adjacent.
+/// 
+/// This is `validate_curve` for an arbitrary sequence of grid points,
+/// e.g. the seeded, reversed or compact variants of the curve.
+/// Returns `Err((i, i+1))` for the first pair of the `i`-th and `(i+1)`-th points which are not adjacent.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_reversed, validate_path};
+/// 
+/// let level = 3;
+/// assert_eq!( Ok(()), validate_path((0..64).map(|h| from_hilbert_index_reversed::<2>(h, level))) );
+/// assert_eq!( Err((1, 2)), validate_path(vec![[0, 0], [0, 1], [1, 2]]) );
+/// ```
+/// 
+pub fn validate_path<const D: usize>(path: impl IntoIterator<Item = [usize; D]>) -> Result<(), (usize, usize)> {
+    let mut path = path.into_iter();
+    let mut prev = match path.next() {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    for (i, p) in path.enumerate() {
+        if manhattan(&prev, &p) != 1 {
+            return Err((i, i + 1));
+        }
+        prev = p;
+    }
+
+    Ok(())
+}
+
+/// The distance along the Hilbert curve between the grid points `a` and `b`,
+/// i.e. the absolute difference of their Hilbert indices.
+/// 
+/// This is a cheap locality proxy: points close on the curve are close in the grid.
+/// However it is not a true metric of the grid, since points adjacent in the grid
+/// may be far on the curve (e.g. across the boundary of the coarsest sub-cubes).
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::curve_distance;
+/// 
+/// let level = 3;
+/// assert_eq!( 1, curve_distance(&[0, 0], &[0, 1], level) );
+/// assert_eq!( 1, curve_distance(&[0, 1], &[0, 0], level) );
+/// // adjacent in the grid, but far on the curve
+/// assert_eq!( 53, curve_distance(&[3, 0], &[4, 0], level) );
+/// ```
+/// 
+pub fn curve_distance<const D: usize>(a: &[usize; D], b: &[usize; D], level: usize) -> usize {
+    let r = curve_interval(a, b, level);
+    r.end - r.start
+}
+
+/// The range of Hilbert indices between the grid points `a` and `b`.
+/// 
+/// The range starts at the smaller index and ends at the larger one (exclusive),
+/// so that its length is `curve_distance(a, b, level)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::curve_interval;
+/// 
+/// let level = 3;
+/// assert_eq!( 1..2, curve_interval(&[1, 1], &[0, 1], level) );
+/// assert_eq!( 1..2, curve_interval(&[0, 1], &[1, 1], level) );
+/// ```
+/// 
+pub fn curve_interval<const D: usize>(a: &[usize; D], b: &[usize; D], level: usize) -> Range<usize> {
+    let (ha, hb) = (a.to_hilbert_index(level), b.to_hilbert_index(level));
+    ha.min(hb)..ha.max(hb)
+}
+
+/// Locality statistics of an ordering of the grid points, computed by `compare_orderings`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalityStats {
+    /// The mean of the index jumps `|order(p) - order(q)|` over all pairs of grid-adjacent points `p` and `q`.
+    pub mean_jump: f64,
+    /// The largest index jump between grid-adjacent points.
+    pub max_jump: usize,
+    /// The largest Manhattan distance between the grid points of consecutive indices.
+    pub max_step: usize,
+}
+
+/// Locality statistics of the Hilbert order and another ordering, computed by `compare_orderings`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderingStats {
+    /// The statistics of the Hilbert order.
+    pub hilbert: LocalityStats,
+    /// The statistics of the other ordering.
+    pub other: LocalityStats,
+}
+
+/// Compare the locality of the Hilbert order with another ordering of the grid points (requires the `alloc` feature).
+/// 
+/// `other` maps each grid point of the box of a given level to its index in the other ordering,
+/// e.g. the Morton (Z-order) or the row-major index, and must be a permutation of `0..2.pow(D*level)`.
+/// Both orderings are measured in two ways (see `LocalityStats`):
+/// how far grid-adjacent points are apart in the index (`mean_jump` and `max_jump`),
+/// and how far consecutive indices are apart in the grid (`max_step`).
+/// The latter is always `1` for the Hilbert order (for `level >= 1`),
+/// while it grows with the size of the box for the Morton and the row-major orders.
+/// On the other hand, no ordering keeps all grid-adjacent points close in the index;
+/// the index jumps of the Hilbert order are not smaller than those of the others on average,
+/// and its largest one grows with the number of cells (see `curve_distance`).
+/// This walks all the grid points and allocates `2.pow(D*level)` points, so it is meant for small boxes.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()` or `other` is not a permutation.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{compare_orderings, to_morton_index};
+/// 
+/// let level = 3;
+/// let stats = compare_orderings::<2>(level, |p| to_morton_index(&p, level));
+/// assert_eq!( 1, stats.hilbert.max_step );
+/// assert_eq!( 8, stats.other.max_step );
+/// assert_eq!( (53, 22), (stats.hilbert.max_jump, stats.other.max_jump) );
+/// ```
+/// 
+#[cfg(feature = "alloc")]
+pub fn compare_orderings<const D: usize>(level: usize, other: impl Fn([usize; D]) -> usize) -> OrderingStats {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    OrderingStats {
+        hilbert: locality_stats::<D>(level, |p| p.to_hilbert_index(level)),
+        other: locality_stats(level, other),
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn locality_stats<const D: usize>(level: usize, order: impl Fn([usize; D]) -> usize) -> LocalityStats {
+    let n = num_cells::<D>(level);
+    // 添字から格子点への逆写像
+    let mut inverse: Vec<Option<[usize; D]>> = vec![None; n];
+    let (mut sum, mut pairs, mut max_jump) = (0u128, 0usize, 0);
+    for p in points::<D>(level) {
+        let h = order(p);
+        assert!(h < n && inverse[h].is_none(), "the ordering is not a permutation of 0..{}: {:?} is mapped to {}", n, p, h);
+        inverse[h] = Some(p);
+
+        for k in 0..D {
+            if p[k] + 1 == 1 << level { continue; }
+            let mut q = p;
+            q[k] += 1;
+            let jump = h.abs_diff(order(q));
+            sum += jump as u128;
+            pairs += 1;
+            max_jump = max_jump.max(jump);
+        }
+    }
+
+    // 全ての添字が使われているので unwrap は失敗しない
+    let max_step = inverse.windows(2)
+        .map(|w| manhattan(&w[0].unwrap(), &w[1].unwrap()))
+        .max().unwrap_or(0);
+    let mean_jump = if pairs == 0 { 0.0 } else { sum as f64 / pairs as f64 };
+
+    LocalityStats { mean_jump, max_jump, max_step }
+}
+
+#[inline]
+fn manhattan<const D: usize>(p: &[usize; D], q: &[usize; D]) -> usize {
+    p.iter().zip(q.iter())
+        .map(|(&a, &b)| (a as isize - b as isize).unsigned_abs())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curve_distance, curve_interval, curve_jump, max_jump_over_range, points, validate_curve, validate_path, FromHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        let n = 1 << (D*level);
+        for h in 0..n {
+            assert_eq!(curve_jump::<D>(h, level), if h + 1 < n { 1 } else { 0 });
+        }
+        assert_eq!(max_jump_over_range::<D>(0, n, level), if n > 1 { 1 } else { 0 });
+        assert_eq!(max_jump_over_range::<D>(n/3, n/2 + 1, level), if n > 2 { 1 } else { 0 });
+    }
+
+    #[test]
+    fn always_one() {
+        for level in 0..6 { check::<2>(level); }
+        for level in 0..4 { check::<3>(level); }
+        check::<5>(2);
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel() {
+        use crate::validate_curve_parallel;
+
+        for level in 0..4 {
+            assert_eq!(validate_curve_parallel::<2>(level), Ok(()));
+            assert_eq!(validate_curve_parallel::<4>(level), Ok(()));
+        }
+        // 複数の shard にまたがる場合
+        assert_eq!(validate_curve_parallel::<2>(9), Ok(()));
+        assert_eq!(validate_curve_parallel::<3>(6), Ok(()));
+        assert_eq!(validate_curve_parallel::<5>(3), Ok(()));
+        assert_eq!(validate_curve_parallel::<1>(20), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_large() {
+        const D: usize = 3;
+        let level = 9;
+        if cfg!(debug_assertions) {
+            // 最適化なしでは全体の検査に時間がかかるので, 最初と最後を含めて shard を間引く.
+            // 全体は `cargo test --release --features rayon` で調べる.
+            let n = super::num_shards::<D>(level);
+            let shards: Vec<usize> = (0..n).step_by(31).chain(n - 1..n).collect();
+            assert_eq!(super::validate_shards::<D>(level, shards), Ok(()));
+        } else {
+            assert_eq!(crate::validate_curve_parallel::<D>(level), Ok(()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn orderings() {
+        for level in 1..6 {
+            let side = 1 << level;
+            let stats = crate::compare_orderings::<2>(level, |[x, y]| x*side + y);
+            // Hilbert 順では連続する添字は常に隣接するが, 行優先順では行の変わり目で飛ぶ
+            assert_eq!(stats.hilbert.max_step, 1);
+            assert_eq!(stats.other.max_step, side);
+            // 隣接する格子の添字の差は, 行優先順では side (縦方向) か 1 (横方向)
+            assert_eq!(stats.other.max_jump, side);
+            assert_eq!(stats.other.mean_jump, (side + 1) as f64 / 2.0);
+            // 最大の添字の差は (10*4^(level-1) - 1)/3 で, 格子の数に比例して増える
+            assert_eq!(stats.hilbert.max_jump, (10*(1 << (2*level - 2)) - 1)/3);
+        }
+
+        let stats = crate::compare_orderings::<3>(0, |_| 0);
+        assert_eq!(stats.hilbert, stats.other);
+        assert_eq!((stats.hilbert.mean_jump, stats.hilbert.max_jump, stats.hilbert.max_step), (0.0, 0, 0));
+
+        let level = 2;
+        let stats = crate::compare_orderings::<3>(level, |p| crate::to_morton_index(&p, level));
+        assert_eq!(stats.hilbert.max_step, 1);
+        assert!(stats.other.max_step > 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic(expected = "is not a permutation")]
+    fn not_permutation() {
+        crate::compare_orderings::<2>(2, |[x, _]| x);
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert_eq!(curve_jump::<2>(usize::MAX, 3), 0);
+        assert_eq!(max_jump_over_range::<2>(63, 100, 3), 0);
+        assert_eq!(max_jump_over_range::<2>(10, 3, 3), 0);
+    }
+
+    #[test]
+    fn distance() {
+        let level = 5;
+        let n = 1 << level;
+        let (mut near, mut total) = (0, 0);
+        for p in points::<2>(level) {
+            assert_eq!(curve_distance(&p, &p, level), 0);
+            // 格子上で隣接する点の組
+            for q in [[p[0] + 1, p[1]], [p[0], p[1] + 1]] {
+                if q[0] >= n || q[1] >= n { continue; }
+                let r = curve_interval(&p, &q, level);
+                assert_eq!(r, curve_interval(&q, &p, level));
+                assert_eq!(r.len(), curve_distance(&p, &q, level));
+                assert!(!r.is_empty());
+                total += 1;
+                if r.len() <= 3 { near += 1; }
+            }
+        }
+        // 必ずしも近いとは限らないが, 大半は近い
+        assert!(near*2 > total, "{} of {}", near, total);
+        assert_eq!(curve_distance(&[0, 0], &[n - 1, 0], level), n*n - 1);
+    }
+
+    #[test]
+    fn validate() {
+        for level in 0..5 {
+            assert_eq!(validate_curve::<2>(level), Ok(()));
+            assert_eq!(validate_curve::<3>(level), Ok(()));
+        }
+        assert_eq!(validate_curve::<6>(2), Ok(()));
+
+        // 曲線の一部を入れ替えると検出される
+        let level = 3;
+        let mut path: Vec<[usize; 2]> = points(level).collect();
+        path.swap(20, 40);
+        assert_eq!(validate_path(path.iter().copied()), Err((19, 20)));
+
+        // seed を与えた曲線も有効
+        let seeded = (0..64).map(|h| h.from_hilbert_index_with(level, 0b10, 1));
+        assert_eq!(validate_path::<2>(seeded), Ok(()));
+        // 同じ点が続くのも不正
+        assert_eq!(validate_path(vec![[0, 0], [0, 0]]), Err((0, 1)));
+        assert_eq!(validate_path::<2>(vec![]), Ok(()));
+    }
+}
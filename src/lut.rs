@@ -0,0 +1,152 @@
+// 2 次元と 3 次元の状態遷移表.
+//
+// 状態 s = e*D + d と, C level 分の入力をまとめた N = 2^(D*C) 通りの値から,
+// 出力と次の状態を引く. 表の要素は (出力) | (次の状態) << 8 である.
+// 入力と出力の並びは, 格子点側が「k 番目の成分の C bit を k*C bit 目から」,
+// Hilbert index 側が「上位 level の w から順に D bit ずつ」である.
+
+use crate::{dmap, emap, gray, t, t_inv};
+
+// 2 次元では 4 level (8 bit), 3 次元では 2 level (6 bit) をまとめて処理する
+pub(crate) static ENCODE_2: [[u16; 256]; 8] = encode_table::<2, 4, 8, 256>();
+pub(crate) static DECODE_2: [[u16; 256]; 8] = decode_table::<2, 4, 8, 256>();
+pub(crate) static ENCODE_3: [[u16; 64]; 24] = encode_table::<3, 2, 24, 64>();
+pub(crate) static DECODE_3: [[u16; 64]; 24] = decode_table::<3, 2, 24, 64>();
+
+const fn encode_table<const D: usize, const C: usize, const S: usize, const N: usize>() -> [[u16; N]; S] {
+    let mut table = [[0; N]; S];
+    let mut s = 0;
+    while s < S {
+        let mut input = 0;
+        while input < N {
+            let (mut e, mut d, mut out) = (s/D, s%D, 0);
+            let mut m = C;
+            while m > 0 {
+                m -= 1;
+                let mut b = 0;
+                let mut k = 0;
+                while k < D {
+                    b |= ((input >> (k*C + m))&1) << k;
+                    k += 1;
+                }
+                let w = gray::decode::<D>(t::<D>(b, e, d));
+                e ^= gray::rotate_left::<D>(emap(w), d+1);
+                d = ( d + dmap::<D>(w) + 1 )%D;
+                out = (out << D) | w;
+            }
+            table[s][input] = (out | (e*D + d) << 8) as u16;
+            input += 1;
+        }
+        s += 1;
+    }
+    table
+}
+
+const fn decode_table<const D: usize, const C: usize, const S: usize, const N: usize>() -> [[u16; N]; S] {
+    let mut table = [[0; N]; S];
+    let mut s = 0;
+    while s < S {
+        let mut input = 0;
+        while input < N {
+            let (mut e, mut d, mut out) = (s/D, s%D, 0);
+            let mut m = C;
+            while m > 0 {
+                m -= 1;
+                let w = (input >> (D*m)) & crate::max::<D>();
+                let l = t_inv::<D>(gray::encode(w), e, d);
+                let mut k = 0;
+                while k < D {
+                    out |= ((l >> k)&1) << (k*C + m);
+                    k += 1;
+                }
+                e ^= gray::rotate_left::<D>(emap(w), d+1);
+                d = ( d + dmap::<D>(w) + 1 )%D;
+            }
+            table[s][input] = (out | (e*D + d) << 8) as u16;
+            input += 1;
+        }
+        s += 1;
+    }
+    table
+}
+
+// level を C の倍数に切り上げるための上位の余分な level 数と, その分だけ戻した初期状態.
+// 余分な level では w = 0 となり, e は変わらず d だけが 1 ずつ進む.
+#[inline]
+fn start<const D: usize, const C: usize>(level: usize) -> (usize, usize) {
+    let pad = (C - level%C)%C;
+    ((level + pad)/C, (D - pad%D)%D)
+}
+
+#[inline]
+pub(crate) fn encode<const D: usize, const C: usize, const N: usize>(table: &[[u16; N]], p: &[usize], level: usize) -> usize {
+    let (chunks, mut s) = start::<D, C>(level);
+    let mask = !( {usize::MAX}<<level );
+    let mut h = 0;
+    for c in (0..chunks).rev() {
+        let input = p.iter().enumerate()
+            .fold(0, |input, (k, x)| input|( ((x & mask) >> (c*C)) & !( {usize::MAX}<<C ) ) << (k*C));
+        let entry = table[s][input];
+        h = (h << (D*C)) | (entry & 0xff) as usize;
+        s = (entry >> 8) as usize;
+    }
+
+    h
+}
+
+#[inline]
+pub(crate) fn decode<const D: usize, const C: usize, const N: usize>(table: &[[u16; N]], h: usize, level: usize, p: &mut [usize]) {
+    let (chunks, mut s) = start::<D, C>(level);
+    let h = h & !( {usize::MAX}<<(D*level) );
+    for c in (0..chunks).rev() {
+        let entry = table[s][(h >> (c*D*C)) & (N - 1)];
+        for (k, x) in p.iter_mut().enumerate() {
+            *x = (*x << C) | (entry as usize >> (k*C)) & !( {usize::MAX}<<C );
+        }
+        s = (entry >> 8) as usize;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_as, indices, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize, step: usize) {
+        for key in indices::<D>(level).step_by(step) {
+            let xyz: [usize; D] = from_hilbert_index_as(key, level);
+            assert_eq!(key.from_hilbert_index(level), xyz);
+            assert_eq!(xyz.to_hilbert_index(level), xyz.to_hilbert_index_as::<usize>(level));
+        }
+    }
+
+    #[test]
+    fn same_as_generic() {
+        for level in 0..=8 {
+            check::<2>(level, 1);
+        }
+        for level in 0..=6 {
+            check::<3>(level, 1);
+        }
+        if cfg!(debug_assertions) {
+            // 最適化なしでは 2^24 点すべての検査に時間がかかるので間引く.
+            // 全体は `cargo test --release` で調べる.
+            check::<3>(8, 97);
+        } else {
+            check::<3>(8, 1);
+        }
+        check::<2>(crate::max_level::<2>(), 1 << 50);
+        check::<3>(crate::max_level::<3>(), 1 << 50);
+    }
+
+    #[test]
+    fn ignores_high_bits() {
+        let level = 5;
+        for key in indices::<2>(level) {
+            let [x, y]: [usize; 2] = key.from_hilbert_index(level);
+            let p = [x | (1 << level), y | (3 << 7)];
+            assert_eq!(p.to_hilbert_index(level), p.to_hilbert_index_as::<usize>(level));
+            let h = key | (1 << (2*level));
+            assert_eq!(FromHilbertIndex::<2>::from_hilbert_index(&h, level), from_hilbert_index_as(h, level));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::DynHilbert;
+
+/// Convert the active components of a grid point to a Hilbert index of the lower dimension.
+/// 
+/// The components `p[k]` with `active[k] == true` are taken in order,
+/// and the result is the Hilbert index of this `m`-dimensional point, where `m` is the number of active axes;
+/// the other components are ignored, as if they were held fixed.
+/// This is useful for the projection of high-dimensional data onto some of its axes,
+/// e.g. marginal queries in spatial databases.
+/// If no axis is active, the result is `0`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{to_hilbert_index_masked, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// let p = [1, 3, 2];
+/// assert_eq!( [1, 2].to_hilbert_index(level), to_hilbert_index_masked(&p, level, [true, false, true]) );
+/// assert_eq!( p.to_hilbert_index(level), to_hilbert_index_masked(&p, level, [true; 3]) );
+/// ```
+/// 
+pub fn to_hilbert_index_masked<const D: usize>(p: &[usize; D], level: usize, active: [bool; D]) -> usize {
+    // 有効な成分を前に詰める
+    let mut q = [0; D];
+    let mut m = 0;
+    for (&x, _) in p.iter().zip(active.iter()).filter(|(_, &a)| a) {
+        q[m] = x;
+        m += 1;
+    }
+    if m == 0 { return 0; }
+    DynHilbert::new(m, level).to_index(&q[..m])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{points, to_hilbert_index_masked, ToHilbertIndex};
+
+    #[test]
+    fn project_3d_to_2d() {
+        let level = 3;
+        for p in points::<3>(level) {
+            let [x, y, z] = p;
+            assert_eq!(to_hilbert_index_masked(&p, level, [true, true, false]), [x, y].to_hilbert_index(level));
+            assert_eq!(to_hilbert_index_masked(&p, level, [true, false, true]), [x, z].to_hilbert_index(level));
+            assert_eq!(to_hilbert_index_masked(&p, level, [false, true, true]), [y, z].to_hilbert_index(level));
+            assert_eq!(to_hilbert_index_masked(&p, level, [false, true, false]), y);
+            assert_eq!(to_hilbert_index_masked(&p, level, [true; 3]), p.to_hilbert_index(level));
+            assert_eq!(to_hilbert_index_masked(&p, level, [false; 3]), 0);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{gray, max, max_level, reduce, FromHilbertIndex, ToHilbertIndex};
+
+// 部分格子 gc(w) の中の Hilbert 曲線の入口.
+// 中心に面した角 c = !gc(w) を基準に, w が偶数なら入口を, 奇数なら出口を c にとる.
+// 出口は入口と第 0 成分だけが異なる (d0 = 0) ので, 隣り合う部分格子の出口と入口が隣接し,
+// 最後の部分格子の出口も最初の部分格子の入口に隣接する.
+#[inline]
+fn entry<const D: usize>(w: usize) -> usize {
+    (!gray::encode(w) & max::<D>()) ^ ((w&1)^1)
+}
+
+/// Convert a grid point `[usize; D]` to an index on the `D`-dimensional Moore curve.
+/// 
+/// The Moore curve is a closed variant of the Hilbert curve for `D >= 2`: the last point `2.pow(D*level) - 1`
+/// is adjacent to the first point `0`, so that wrapping around the indices stays spatially local.
+/// A closed path is impossible for `D == 1`, where the index is just the coordinate `p[0]`
+/// as in `to_hilbert_index`, and the curve is not closed for `level >= 2`.
+/// It visits the `2.pow(D)` sub-cubes of level `level - 1` in the Gray code order,
+/// and each of them along a Hilbert curve (see `to_hilbert_index_with`) whose entry and exit
+/// lie next to the center of the box. Adjacent indices give adjacent grid points as well.
+/// The inverse is `from_moore_index`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_moore_index, to_moore_index};
+/// 
+/// let level = 2;
+/// let first: [usize; 2] = from_moore_index(0, level);
+/// let last: [usize; 2] = from_moore_index(15, level);
+/// assert_eq!( [[0, 1], [0, 2]], [first, last] );
+/// assert_eq!( 15, to_moore_index(&[0, 2], level) );
+/// ```
+/// 
+pub fn to_moore_index<const D: usize>(p: &[usize; D], level: usize) -> usize {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    if level == 0 { return 0; }
+    let w = gray::decode::<D>(reduce(p, level - 1));
+    let mask = !( {usize::MAX}<<(level - 1) );
+    let mut q = *p;
+    for x in q.iter_mut() {
+        *x &= mask;
+    }
+    (w << (D*(level - 1))) | q.to_hilbert_index_with(level - 1, entry::<D>(w), 0)
+}
+
+/// Convert an index on the `D`-dimensional Moore curve to a grid point `[usize; D]`.
+/// 
+/// This is the inverse of `to_moore_index`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_moore_index, to_moore_index};
+/// 
+/// let level = 3;
+/// let p: [usize; 3] = from_moore_index(100, level);
+/// assert_eq!( 100, to_moore_index(&p, level) );
+/// ```
+/// 
+pub fn from_moore_index<const D: usize>(h: usize, level: usize) -> [usize; D] {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    if level == 0 { return [0; D]; }
+    let w = (h >> (D*(level - 1))) & max::<D>();
+    let c = gray::encode(w);
+    let mut p: [usize; D] = (h & !( {usize::MAX}<<(D*(level - 1)) ))
+        .from_hilbert_index_with(level - 1, entry::<D>(w), 0);
+    for (k, x) in p.iter_mut().enumerate() {
+        *x |= ((c >> k)&1) << (level - 1);
+    }
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_moore_index, to_moore_index};
+
+    fn check<const D: usize>(level: usize) {
+        let n = 1usize << (D*level);
+        let mut visited = vec![false; n];
+        let adjacent = |p: &[usize; D], q: &[usize; D]| p.iter().zip(q.iter())
+            .map(|(&a, &b)| (a as isize - b as isize).abs())
+            .sum::<isize>() == 1;
+
+        for h in 0..n {
+            let p: [usize; D] = from_moore_index(h, level);
+            assert_eq!(h, to_moore_index(&p, level));
+
+            let flat = p.iter().fold(0, |f, &x| (f << level) | x);
+            assert!(!visited[flat]);
+            visited[flat] = true;
+
+            // 最後の点の次は最初の点で, 曲線は閉じている
+            let q: [usize; D] = from_moore_index((h + 1)%n, level);
+            assert!(adjacent(&p, &q), "{:?} and {:?} are not adjacent", p, q);
+        }
+    }
+
+    #[test]
+    fn closed_loop() {
+        for level in 1..6 { check::<2>(level); }
+        for level in 1..4 { check::<3>(level); }
+        check::<4>(2);
+        check::<5>(2);
+    }
+
+    #[test]
+    fn one_dimension() {
+        // 1 次元では恒等写像の開いた道になる
+        for level in 0..5 {
+            for h in 0..1 << level {
+                assert_eq!(from_moore_index::<1>(h, level), [h]);
+                assert_eq!(to_moore_index(&[h], level), h);
+            }
+        }
+    }
+
+    #[test]
+    fn level_zero() {
+        assert_eq!(to_moore_index(&[0, 0], 0), 0);
+        assert_eq!(from_moore_index::<2>(0, 0), [0, 0]);
+    }
+}
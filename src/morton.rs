@@ -0,0 +1,70 @@
+use crate::{max, reduce, FromHilbertIndex, ToHilbertIndex};
+
+/// Convert a grid point `[usize; D]` to a Morton (Z-order) index.
+/// 
+/// The bits of the components are interleaved: the `k`-th component gives
+/// the `(D*i + k)`-th bit of the index for `i` in `0..level`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::to_morton_index;
+/// 
+/// let level = 1;
+/// assert_eq!( 0, to_morton_index(&[0, 0], level) );
+/// assert_eq!( 1, to_morton_index(&[1, 0], level) );
+/// assert_eq!( 2, to_morton_index(&[0, 1], level) );
+/// assert_eq!( 3, to_morton_index(&[1, 1], level) );
+/// ```
+/// 
+pub fn to_morton_index<const D: usize>(p: &[usize; D], level: usize) -> usize {
+    (0..level).rev().fold(0, |z, i| (z << D) | reduce(p, i))
+}
+
+/// Convert a Morton (Z-order) index to a grid point `[usize; D]`.
+/// 
+/// This is the inverse of `to_morton_index`.
+pub fn from_morton_index<const D: usize>(z: usize, level: usize) -> [usize; D] {
+    let mut p = [0; D];
+    for i in (0..level).rev() {
+        let w = (z >> (i*D)) & max::<D>();
+        for (j, x) in p.iter_mut().enumerate() {
+            *x = (*x << 1)|((w >> j)&1);
+        }
+    }
+    p
+}
+
+/// Convert a Hilbert index to the Morton index of the same grid point.
+pub fn hilbert_to_morton<const D: usize>(h: usize, level: usize) -> usize {
+    let p: [usize; D] = h.from_hilbert_index(level);
+    to_morton_index(&p, level)
+}
+
+/// Convert a Morton index to the Hilbert index of the same grid point.
+pub fn morton_to_hilbert<const D: usize>(z: usize, level: usize) -> usize {
+    from_morton_index::<D>(z, level).to_hilbert_index(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_morton_index, hilbert_to_morton, indices, morton_to_hilbert, to_morton_index};
+
+    #[test]
+    fn round_trip() {
+        const D: usize = 2;
+        let level = 3;
+        for z in indices::<D>(level) {
+            let p: [usize; D] = from_morton_index(z, level);
+            assert_eq!(z, to_morton_index(&p, level));
+            assert_eq!(z, hilbert_to_morton::<D>(morton_to_hilbert::<D>(z, level), level));
+        }
+    }
+
+    #[test]
+    fn differ_from_hilbert() {
+        const D: usize = 2;
+        let level = 3;
+        assert!(indices::<D>(level).any(|h| hilbert_to_morton::<D>(h, level) != h));
+    }
+}
@@ -0,0 +1,47 @@
+use nalgebra::{Point, SVector};
+use crate::ToHilbertIndex;
+
+// nalgebra の型を配列に変換すれば, 残りは trait の既定の実装が与える
+impl<const D: usize> ToHilbertIndex<D> for SVector<usize, D> {
+    #[inline]
+    fn to_grid_point(&self) -> [usize; D] {
+        (*self).into()
+    }
+}
+
+impl<const D: usize> ToHilbertIndex<D> for Point<usize, D> {
+    #[inline]
+    fn to_grid_point(&self) -> [usize; D] {
+        self.coords.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Point, Point2, Point3, SVector, Vector2};
+    use crate::{indices, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        for h in indices::<D>(level) {
+            let p: [usize; D] = h.from_hilbert_index(level);
+            assert_eq!(Point::from(p).to_hilbert_index(level), h);
+            assert_eq!(SVector::from(p).to_hilbert_index(level), h);
+            assert_eq!(Point::from(p).to_compact_hilbert_index([level; D]), h);
+            assert_eq!(SVector::from(p).try_to_hilbert_index(level), Ok(h));
+        }
+    }
+
+    #[test]
+    fn same_as_array() {
+        check::<2>(4);
+        check::<3>(3);
+        check::<5>(2);
+
+        let level = 3;
+        assert_eq!(Point2::new(3, 5).to_hilbert_index(level), [3, 5].to_hilbert_index(level));
+        assert_eq!(Vector2::new(3, 5).to_hilbert_index(level), [3, 5].to_hilbert_index(level));
+        let p = Point3::new(1, 2, 3);
+        let h = p.to_hilbert_index(level);
+        assert_eq!(Point3::from(FromHilbertIndex::<3>::from_hilbert_index(&h, level)), p);
+    }
+}
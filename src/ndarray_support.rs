@@ -0,0 +1,90 @@
+use alloc::{vec, vec::Vec};
+use crate::dynamic::from_compact_index_into;
+
+/// The multi-indices of an array of a given `shape`, in the Hilbert order
+/// (requires the `ndarray` and `alloc` features).
+/// 
+/// The shape is rounded up to a box whose `k`-th side is the next power of two of `shape[k]`,
+/// and the cells of the box are visited along its compact Hilbert curve (see `to_compact_hilbert_index`),
+/// skipping the cells out of `shape`. Hence every element of the array appears exactly once,
+/// and non-cubic shapes are handled without visiting a cube of the largest side.
+/// The dimension is given at runtime by `shape.len()`, so this works with `ndarray::ArrayD`.
+/// Iterating elements in this order improves cache locality, e.g. for stencil computations.
+/// 
+/// # Panics
+/// 
+/// Panics if the rounded box has `2.pow(usize::BITS)` or more cells.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::hilbert_index_order;
+/// use ndarray::ArrayD;
+/// 
+/// let a = ArrayD::<f64>::zeros(vec![7, 5]);
+/// let order = hilbert_index_order(a.shape());
+/// assert_eq!( a.len(), order.len() );
+/// assert_eq!( vec![0, 0], order[0] );
+/// let sum: f64 = order.iter().map(|idx| a[&idx[..]]).sum();
+/// assert_eq!( 0.0, sum );
+/// ```
+/// 
+pub fn hilbert_index_order(shape: &[usize]) -> Vec<Vec<usize>> {
+    if shape.contains(&0) { return Vec::new(); }
+
+    // k 番目の成分は levels[k] bit あれば足りる
+    let levels: Vec<usize> = shape.iter()
+        .map(|&n| (usize::BITS - (n - 1).leading_zeros()) as usize)
+        .collect();
+    let bits: usize = levels.iter().sum();
+    assert!(bits < usize::BITS as usize, "shape {:?} needs {} bits, which do not fit in usize", shape, bits);
+
+    let mut order = Vec::with_capacity(shape.iter().product());
+    let mut p = vec![0; shape.len()];
+    for h in 0..1usize << bits {
+        from_compact_index_into(h, &levels, &mut p);
+        if p.iter().zip(shape.iter()).all(|(x, n)| x < n) {
+            order.push(p.clone());
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array2, ArrayD};
+    use crate::hilbert_index_order;
+
+    #[test]
+    fn visits_every_cell_once() {
+        let mut a = Array2::<usize>::zeros((7, 5));
+        let order = hilbert_index_order(a.shape());
+        assert_eq!(order.len(), 35);
+        for idx in order.iter() {
+            a[[idx[0], idx[1]]] += 1;
+        }
+        assert!(a.iter().all(|&n| n == 1));
+
+        // 箱の中で隣接する cell が続くことが多い
+        let near = order.windows(2)
+            .filter(|w| w[0].iter().zip(w[1].iter())
+                .map(|(&x, &y)| (x as isize - y as isize).abs())
+                .sum::<isize>() == 1)
+            .count();
+        assert!(2*near > order.len());
+    }
+
+    #[test]
+    fn dynamic_dimension() {
+        let mut a = ArrayD::<usize>::zeros(vec![3, 1, 4, 2]);
+        for idx in hilbert_index_order(a.shape()) {
+            a[&idx[..]] += 1;
+        }
+        assert!(a.iter().all(|&n| n == 1));
+
+        assert_eq!(hilbert_index_order(&[4, 4]).len(), 16);
+        assert!(hilbert_index_order(&[3, 0]).is_empty());
+        assert_eq!(hilbert_index_order(&[]), vec![Vec::<usize>::new()]);
+    }
+}
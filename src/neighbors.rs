@@ -0,0 +1,143 @@
+use alloc::vec::Vec;
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// Get the Hilbert indices of the grid cells adjacent to the cell `h` along each axis.
+/// 
+/// The grid point of `h` is perturbed by `-1` and `+1` along every axis (in this order, axis by axis),
+/// and the cells outside `[0, 2.pow(level))` are skipped.
+/// Hence the result has at most `2*D` elements.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{neighbors, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let h = [0, 0].to_hilbert_index(level);
+/// let n = neighbors::<2>(h, level);
+/// assert_eq!( vec![[1, 0].to_hilbert_index(level), [0, 1].to_hilbert_index(level)], n );
+/// ```
+/// 
+pub fn neighbors<const D: usize>(h: usize, level: usize) -> Vec<usize> {
+    let p: [usize; D] = h.from_hilbert_index(level);
+    let bound = 1 << level;
+    let mut n = Vec::with_capacity(2*D);
+
+    for k in 0..D {
+        if p[k] > 0 {
+            let mut q = p;
+            q[k] -= 1;
+            n.push(q.to_hilbert_index(level));
+        }
+        if p[k] + 1 < bound {
+            let mut q = p;
+            q[k] += 1;
+            n.push(q.to_hilbert_index(level));
+        }
+    }
+
+    n
+}
+
+/// Toroidal version of `neighbors`, for grids with periodic boundaries.
+/// 
+/// Stepping off the box wraps around on that axis: `-1` from `0` is `2.pow(level) - 1`, and `+1` from
+/// `2.pow(level) - 1` is `0`. The Hilbert indices are those of the wrapped grid points.
+/// Hence every cell has exactly `2*D` neighbors for `level >= 2`;
+/// for `level == 1` the two neighbors along an axis coincide and are listed once,
+/// and for `level == 0` there are none.
+/// 
+/// Note that the wrapped neighbors are generally far from `h` in the Hilbert order,
+/// since the Hilbert curve is a path in the box, not in the torus.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{neighbors_toroidal, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let h = [0, 0].to_hilbert_index(level);
+/// let n = neighbors_toroidal::<2>(h, level);
+/// assert_eq!( 4, n.len() );
+/// assert!( n.contains(&[7, 0].to_hilbert_index(level)) );
+/// ```
+/// 
+pub fn neighbors_toroidal<const D: usize>(h: usize, level: usize) -> Vec<usize> {
+    let p: [usize; D] = h.from_hilbert_index(level);
+    let bound = 1 << level;
+    let mut n = Vec::with_capacity(2*D);
+
+    for k in 0..D {
+        let (prv, nxt) = ((p[k] + bound - 1)%bound, (p[k] + 1)%bound);
+        if prv != p[k] {
+            let mut q = p;
+            q[k] = prv;
+            n.push(q.to_hilbert_index(level));
+        }
+        if nxt != p[k] && nxt != prv {
+            let mut q = p;
+            q[k] = nxt;
+            n.push(q.to_hilbert_index(level));
+        }
+    }
+
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, neighbors, neighbors_toroidal, FromHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn corner_and_interior() {
+        let level = 3;
+        assert_eq!(neighbors::<2>([0, 0].to_hilbert_index(level), level).len(), 2);
+        assert_eq!(neighbors::<2>([7, 0].to_hilbert_index(level), level).len(), 2);
+        assert_eq!(neighbors::<2>([0, 4].to_hilbert_index(level), level).len(), 3);
+        assert_eq!(neighbors::<2>([3, 5].to_hilbert_index(level), level).len(), 4);
+    }
+
+    #[test]
+    fn adjacent() {
+        const D: usize = 3;
+        let level = 2;
+        for h in indices::<D>(level) {
+            let p: [usize; D] = h.from_hilbert_index(level);
+            let n = neighbors::<D>(h, level);
+
+            // 各成分について端にいなければ 2 つずつ隣接点がある
+            let expected = p.iter().map(|&x| if x == 0 || x == 3 { 1 } else { 2 }).sum::<usize>();
+            assert_eq!(n.len(), expected);
+
+            for &m in n.iter() {
+                let q: [usize; D] = m.from_hilbert_index(level);
+                let diff = p.iter().zip(q.iter())
+                    .map(|(&a, &b)| (a as isize - b as isize).abs())
+                    .sum::<isize>();
+                assert_eq!(diff, 1);
+            }
+        }
+    }
+
+    #[test]
+    fn toroidal() {
+        let level = 3;
+        for p in [[0, 0], [7, 0], [0, 7], [7, 7]].iter() {
+            assert_eq!(neighbors_toroidal::<2>(p.to_hilbert_index(level), level).len(), 4);
+        }
+        let mut n = neighbors_toroidal::<2>([0, 0].to_hilbert_index(level), level);
+        let mut expected: Vec<usize> = [[7, 0], [1, 0], [0, 7], [0, 1]].iter()
+            .map(|q| q.to_hilbert_index(level))
+            .collect();
+        n.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(n, expected);
+
+        // 内部の点では neighbors と一致する
+        let h = [3, 5].to_hilbert_index(level);
+        assert_eq!(neighbors_toroidal::<2>(h, level), neighbors::<2>(h, level));
+
+        assert_eq!(neighbors_toroidal::<3>(0, 1).len(), 3);
+        assert_eq!(neighbors_toroidal::<3>(0, 0).len(), 0);
+    }
+}
@@ -0,0 +1,107 @@
+use core::cmp::Ordering;
+use crate::ToHilbertIndex;
+
+/// A grid point which is ordered by its Hilbert index.
+/// 
+/// The Hilbert index is computed once by `new` and cached,
+/// so that comparisons do not recompute it. This allows to use grid points as keys of
+/// sorted containers such as `BTreeMap` and `BTreeSet`, which are then iterated in the Hilbert order.
+/// 
+/// All keys compared with each other must share the same `level`;
+/// the order of keys with different levels is not meaningful (it is rejected by debug assertions).
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertOrd;
+/// use std::collections::BTreeSet;
+/// 
+/// let level = 1;
+/// let set: BTreeSet<_> = [[1, 0], [0, 0], [1, 1], [0, 1]].iter()
+///     .map(|&p| HilbertOrd::new(p, level))
+///     .collect();
+/// let points: Vec<_> = set.iter().map(|k| k.point()).collect();
+/// assert_eq!( vec![[0, 0], [0, 1], [1, 1], [1, 0]], points );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HilbertOrd<const D: usize> {
+    point: [usize; D],
+    level: usize,
+    index: usize,
+}
+
+impl<const D: usize> HilbertOrd<D> {
+    /// Wrap a grid point, computing its Hilbert index at `level`.
+    pub fn new(point: [usize; D], level: usize) -> Self {
+        HilbertOrd { point, level, index: point.to_hilbert_index(level) }
+    }
+
+    /// The grid point.
+    pub fn point(&self) -> [usize; D] { self.point }
+
+    /// The level of the curve.
+    pub fn level(&self) -> usize { self.level }
+
+    /// The cached Hilbert index of the grid point.
+    pub fn index(&self) -> usize { self.index }
+}
+
+impl<const D: usize> Ord for HilbertOrd<D> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        debug_assert_eq!(self.level, other.level, "HilbertOrd: compared keys must share the same level");
+        // level が等しければ添字の順. そうでなくても全順序になるよう level で比較する.
+        self.index.cmp(&other.index)
+            .then(self.level.cmp(&other.level))
+    }
+}
+
+impl<const D: usize> PartialOrd for HilbertOrd<D> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+    use crate::{indices, points, FromHilbertIndex, HilbertOrd};
+
+    #[test]
+    fn btree_set() {
+        let level = 3;
+        // Hilbert 順とは異なる順で挿入する
+        let mut set = BTreeSet::new();
+        for p in points::<2>(level).collect::<Vec<_>>().into_iter().rev() {
+            set.insert(HilbertOrd::new(p, level));
+        }
+        for x in 0..8 {
+            assert!(!set.insert(HilbertOrd::new([x, x], level)));
+        }
+
+        assert_eq!(set.len(), 64);
+        for (key, h) in set.iter().zip(indices::<2>(level)) {
+            assert_eq!(key.index(), h);
+            assert_eq!(key.point(), h.from_hilbert_index(level));
+            assert_eq!(key.level(), level);
+        }
+    }
+
+    #[test]
+    fn btree_map() {
+        let level = 2;
+        let mut map = BTreeMap::new();
+        map.insert(HilbertOrd::new([3, 0, 0], level), "last");
+        map.insert(HilbertOrd::new([0, 0, 0], level), "first");
+        map.insert(HilbertOrd::new([1, 1, 1], level), "middle");
+        let values: Vec<_> = map.values().copied().collect();
+        assert_eq!(values, ["first", "middle", "last"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "share the same level")]
+    #[cfg(debug_assertions)]
+    fn different_levels() {
+        let _ = HilbertOrd::new([0, 0], 1) < HilbertOrd::new([0, 0], 2);
+    }
+}
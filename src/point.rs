@@ -0,0 +1,183 @@
+use core::fmt;
+use core::num::ParseIntError;
+use core::ops::{Deref, DerefMut};
+use core::str::FromStr;
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// A grid point `[usize; D]`.
+/// 
+/// It is displayed as `x,y,z`, parsed from the same format by `FromStr`,
+/// and dereferences to the inner array.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{Index, Point};
+/// 
+/// let level = 1;
+/// let p = Point([1, 1, 0]);
+/// assert_eq!( Index(6), p.to_index(level) );
+/// assert_eq!( "1,1,0", p.to_string() );
+/// assert_eq!( Ok(p), "1,1,0".parse() );
+/// assert_eq!( 1, p[0] );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point<const D: usize>(pub [usize; D]);
+
+/// A Hilbert index `usize`.
+/// 
+/// It is displayed as a decimal number.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{Index, Point};
+/// 
+/// let level = 1;
+/// assert_eq!( Point([1, 1, 0]), Index(6).to_point::<3>(level) );
+/// assert_eq!( "6", Index(6).to_string() );
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Index(pub usize);
+
+impl<const D: usize> Point<D> {
+    /// Convert to the Hilbert index.
+    pub fn to_index(&self, level: usize) -> Index {
+        Index(self.0.to_hilbert_index(level))
+    }
+}
+
+impl Index {
+    /// Convert to the grid point.
+    pub fn to_point<const D: usize>(&self, level: usize) -> Point<D> {
+        Point(self.0.from_hilbert_index(level))
+    }
+}
+
+impl<const D: usize> Deref for Point<D> {
+    type Target = [usize; D];
+
+    fn deref(&self) -> &[usize; D] {
+        &self.0
+    }
+}
+
+impl<const D: usize> DerefMut for Point<D> {
+    fn deref_mut(&mut self) -> &mut [usize; D] {
+        &mut self.0
+    }
+}
+
+impl<const D: usize> From<[usize; D]> for Point<D> {
+    fn from(p: [usize; D]) -> Self {
+        Point(p)
+    }
+}
+
+impl From<usize> for Index {
+    fn from(h: usize) -> Self {
+        Index(h)
+    }
+}
+
+impl<const D: usize> fmt::Display for Point<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (k, x) in self.0.iter().enumerate() {
+            if k > 0 { write!(f, ",")?; }
+            write!(f, "{}", x)?;
+        }
+        Ok(())
+    }
+}
+
+/// An error returned when parsing a `Point` from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    /// The string has `found` comma-separated components instead of `expected`.
+    WrongCount { expected: usize, found: usize },
+    /// The `dim`-th component is not a valid `usize`.
+    InvalidComponent { dim: usize, source: ParseIntError },
+}
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsePointError::WrongCount { expected, found } => {
+                write!(f, "expected {} components, but found {}", expected, found)
+            },
+            ParsePointError::InvalidComponent { dim, source } => {
+                write!(f, "component {} is invalid: {}", dim, source)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePointError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParsePointError::InvalidComponent { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Parse `x,y,z`, the format of `Display`. Whitespace around each component is ignored.
+impl<const D: usize> FromStr for Point<D> {
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, ParsePointError> {
+        let found = s.split(',').count();
+        if found != D {
+            return Err(ParsePointError::WrongCount { expected: D, found });
+        }
+
+        let mut p = [0; D];
+        for (dim, (x, c)) in p.iter_mut().zip(s.split(',')).enumerate() {
+            *x = c.trim().parse().map_err(|source| ParsePointError::InvalidComponent { dim, source })?;
+        }
+        Ok(Point(p))
+    }
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, Index, ParsePointError, Point};
+
+    #[test]
+    fn round_trip() {
+        let level = 2;
+        for h in indices::<3>(level) {
+            let p = Index(h).to_point::<3>(level);
+            assert_eq!(p.to_index(level), Index(h));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Point([3, 5, 7]).to_string(), "3,5,7");
+        assert_eq!(Point([3]).to_string(), "3");
+        assert_eq!(Index(42).to_string(), "42");
+    }
+
+    #[test]
+    fn parse() {
+        let p: Point<3> = "3,5,7".parse().unwrap();
+        assert_eq!(p, Point([3, 5, 7]));
+        assert_eq!(p.to_string().parse(), Ok(p));
+        assert_eq!(" 3, 5 ,7 ".parse(), Ok(p));
+
+        assert_eq!("3,5".parse::<Point<3>>(), Err(ParsePointError::WrongCount { expected: 3, found: 2 }));
+        assert_eq!("3,5,7,9".parse::<Point<3>>(), Err(ParsePointError::WrongCount { expected: 3, found: 4 }));
+        assert!(matches!("3,-5,7".parse::<Point<3>>(), Err(ParsePointError::InvalidComponent { dim: 1, .. })));
+        assert!(matches!("".parse::<Point<1>>(), Err(ParsePointError::InvalidComponent { dim: 0, .. })));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{Point, ToHilbertIndex};
+
+/// Types which have a position on the grid, such as particles or agents of a simulation.
+/// 
+/// Implementing `grid_position` gives `hilbert_key`, the Hilbert index of the position,
+/// so that a collection of such values can be keyed without building intermediate arrays by hand.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{HasGridPosition, ToHilbertIndex};
+/// 
+/// struct Particle {
+///     position: [usize; 3],
+///     mass: f64,
+/// }
+/// 
+/// impl HasGridPosition<3> for Particle {
+///     fn grid_position(&self) -> [usize; 3] { self.position }
+/// }
+/// 
+/// let level = 2;
+/// let particles = vec![
+///     Particle { position: [1, 2, 3], mass: 1.0 },
+///     Particle { position: [0, 0, 1], mass: 2.0 },
+/// ];
+/// let keys: Vec<usize> = particles.iter().map(|p| p.hilbert_key(level)).collect();
+/// assert_eq!( vec![[1, 2, 3].to_hilbert_index(level), [0, 0, 1].to_hilbert_index(level)], keys );
+/// ```
+/// 
+pub trait HasGridPosition<const D: usize> {
+    /// The grid point of `self`, whose components are less than `2.pow(level)`.
+    fn grid_position(&self) -> [usize; D];
+
+    /// The Hilbert index of `grid_position()`.
+    /// 
+    /// This is `self.grid_position().to_hilbert_index(level)`.
+    #[inline]
+    fn hilbert_key(&self, level: usize) -> usize {
+        self.grid_position().to_hilbert_index(level)
+    }
+}
+
+impl<const D: usize> HasGridPosition<D> for [usize; D] {
+    #[inline]
+    fn grid_position(&self) -> [usize; D] { *self }
+}
+
+impl<const D: usize> HasGridPosition<D> for Point<D> {
+    #[inline]
+    fn grid_position(&self) -> [usize; D] { self.0 }
+}
+
+impl<T: HasGridPosition<D> + ?Sized, const D: usize> HasGridPosition<D> for &T {
+    #[inline]
+    fn grid_position(&self) -> [usize; D] { (**self).grid_position() }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{points, HasGridPosition, Point, ToHilbertIndex};
+
+    struct Agent {
+        x: usize,
+        y: usize,
+    }
+
+    impl HasGridPosition<2> for Agent {
+        fn grid_position(&self) -> [usize; 2] { [self.x, self.y] }
+    }
+
+    fn key<T: HasGridPosition<2>>(t: T, level: usize) -> usize { t.hilbert_key(level) }
+
+    #[test]
+    fn same_as_array() {
+        let level = 3;
+        for p in points::<2>(level) {
+            let agent = Agent { x: p[0], y: p[1] };
+            assert_eq!(agent.hilbert_key(level), p.to_hilbert_index(level));
+            assert_eq!(key(&agent, level), p.to_hilbert_index(level));
+            assert_eq!(HasGridPosition::hilbert_key(&p, level), p.to_hilbert_index(level));
+            assert_eq!(Point(p).hilbert_key(level), p.to_hilbert_index(level));
+        }
+    }
+}
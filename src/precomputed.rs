@@ -0,0 +1,241 @@
+use crate::{dmap, emap, gray, lut, max_level, reduce, t, t_inv};
+
+// テーブルを持つ次元の上限. 2^D 個の w について値を保持する.
+const TABLE_DIM: usize = 8;
+const TABLE_LEN: usize = 1 << TABLE_DIM;
+
+/// A `D`-dimensional Hilbert curve of a fixed level with precomputed tables.
+/// 
+/// The maps `emap`, `dmap` and the inverse Gray code of all `2.pow(D)` words are
+/// computed once by `new`, so that `encode` and `decode` only look them up.
+/// For `3 < D <= 8`, only `encode` gains from the tables, since it saves the inverse Gray code:
+/// it is about 10% faster than `to_hilbert_index` at `D = 5` and level 12.
+/// `decode` needs only the cheap Gray code, so it is no faster than `from_hilbert_index`.
+/// For larger `D` the maps are computed on each call.
+/// For `D = 2, 3`, `encode` and `decode` use the same state transition tables as the traits,
+/// which process several levels at once, so they are not faster than the traits
+/// and the axis permutation adds a small overhead (see `benches/hilbert.rs`).
+/// The results are identical to `to_hilbert_index` and `from_hilbert_index`,
+/// unless the axes are permuted by `with_axis_order`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertTransform;
+/// 
+/// let curve = HilbertTransform::<2>::new(3);
+/// 
+/// assert_eq!( 2, curve.encode(&[1, 1]) );
+/// assert_eq!( [1, 1], curve.decode(2) );
+/// ```
+/// 
+#[derive(Clone)]
+pub struct HilbertTransform<const D: usize> {
+    level: usize,
+    axis_order: [usize; D],
+    gc_inv: [u8; TABLE_LEN],
+    emap: [u8; TABLE_LEN],
+    dmap: [u8; TABLE_LEN],
+}
+
+impl<const D: usize> HilbertTransform<D> {
+    /// Create the tables for a given level.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `level > max_level::<D>()`.
+    pub fn new(level: usize) -> Self {
+        assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+        let mut curve = HilbertTransform {
+            level,
+            axis_order: [0; D],
+            gc_inv: [0; TABLE_LEN],
+            emap: [0; TABLE_LEN],
+            dmap: [0; TABLE_LEN],
+        };
+        for (k, a) in curve.axis_order.iter_mut().enumerate() {
+            *a = k;
+        }
+        if D <= TABLE_DIM {
+            for w in 0..1 << D {
+                curve.gc_inv[w] = gray::decode::<D>(w) as u8;
+                curve.emap[w] = emap(w) as u8;
+                curve.dmap[w] = dmap::<D>(w) as u8;
+            }
+        }
+        curve
+    }
+
+    /// Permute the axes: the `k`-th dimension of the curve is taken from the component `axis_order[k]` of grid points.
+    /// 
+    /// `encode` applies the permutation before computing the index, and `decode` inverts it,
+    /// so that grid points are always given in the caller's convention.
+    /// This is useful to match another library which uses a different axis as the first one.
+    /// The identity `[0, 1, .., D-1]` (default) gives the same results as `to_hilbert_index`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `axis_order` is not a permutation of `0..D`.
+    /// 
+    /// # Usage
+    /// 
+    /// ```
+    /// use hilbert_index::{HilbertTransform, ToHilbertIndex};
+    /// 
+    /// let level = 3;
+    /// let curve = HilbertTransform::<2>::new(level).with_axis_order([1, 0]);
+    /// assert_eq!( [1, 0].to_hilbert_index(level), curve.encode(&[0, 1]) );
+    /// assert_eq!( [0, 1], curve.decode(curve.encode(&[0, 1])) );
+    /// ```
+    /// 
+    pub fn with_axis_order(mut self, axis_order: [usize; D]) -> Self {
+        let mut seen = [false; D];
+        for &a in axis_order.iter() {
+            assert!(a < D && !seen[a], "axis_order {:?} is not a permutation of 0..{}", axis_order, D);
+            seen[a] = true;
+        }
+        self.axis_order = axis_order;
+        self
+    }
+
+    /// The level of the curve.
+    pub fn level(&self) -> usize { self.level }
+
+    /// The order of the axes set by `with_axis_order`.
+    pub fn axis_order(&self) -> [usize; D] { self.axis_order }
+
+    #[inline]
+    fn lookup(&self, w: usize) -> (usize, usize) {
+        if D <= TABLE_DIM {
+            (self.emap[w] as usize, self.dmap[w] as usize)
+        } else {
+            (emap(w), dmap::<D>(w))
+        }
+    }
+
+    /// Convert a grid point to a Hilbert index, same as `to_hilbert_index(self.level())` with the axes permuted.
+    #[inline]
+    pub fn encode(&self, p: &[usize; D]) -> usize {
+        let mut q = [0; D];
+        for (x, &a) in q.iter_mut().zip(self.axis_order.iter()) {
+            *x = p[a];
+        }
+        let p = &q;
+        // 2 次元と 3 次元は trait と同じ状態遷移表の方が速い
+        match D {
+            2 => return lut::encode::<2, 4, 256>(&lut::ENCODE_2, p, self.level),
+            3 => return lut::encode::<3, 2, 64>(&lut::ENCODE_3, p, self.level),
+            _ => {},
+        }
+        let (mut h, mut e, mut d) = (0, 0, 0);
+        for i in (0..self.level).rev() {
+            let l = t::<D>(reduce(p, i), e, d);
+            let w = if D <= TABLE_DIM { self.gc_inv[l] as usize } else { gray::decode::<D>(l) };
+            let (em, dm) = self.lookup(w);
+            e ^= gray::rotate_left::<D>(em, d+1);
+            d = ( d + dm + 1 )%D;
+            h = (h << D) | w;
+        }
+
+        h
+    }
+
+    /// Convert a Hilbert index to a grid point, same as `from_hilbert_index(self.level())` with the axes permuted.
+    #[inline]
+    pub fn decode(&self, h: usize) -> [usize; D] {
+        let mut p = [0; D];
+        // 2 次元と 3 次元は trait と同じ状態遷移表の方が速い
+        if D == 2 || D == 3 {
+            let mut q = [0; D];
+            if D == 2 {
+                lut::decode::<2, 4, 256>(&lut::DECODE_2, h, self.level, &mut q);
+            } else {
+                lut::decode::<3, 2, 64>(&lut::DECODE_3, h, self.level, &mut q);
+            }
+            for (&x, &a) in q.iter().zip(self.axis_order.iter()) {
+                p[a] = x;
+            }
+            return p;
+        }
+
+        let (mut e, mut d) = (0, 0);
+        for i in (0..self.level).rev() {
+            let w = (h >> (i*D)) & crate::max::<D>();
+            let l = t_inv::<D>(gray::encode(w), e, d);
+            for (j, &a) in self.axis_order.iter().enumerate() {
+                p[a] = (p[a] << 1)|((l >> j)&1);
+            }
+            let (em, dm) = self.lookup(w);
+            e ^= gray::rotate_left::<D>(em, d+1);
+            d = ( d + dm + 1 )%D;
+        }
+
+        p
+    }
+}
+
+impl<const D: usize> core::fmt::Debug for HilbertTransform<D> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HilbertTransform")
+            .field("dimension", &D)
+            .field("level", &self.level)
+            .field("axis_order", &self.axis_order)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, FromHilbertIndex, HilbertTransform, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        let curve = HilbertTransform::<D>::new(level);
+        for key in indices::<D>(level) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(curve.decode(key), xyz);
+            assert_eq!(curve.encode(&xyz), xyz.to_hilbert_index(level));
+        }
+    }
+
+    #[test]
+    fn same_as_traits() {
+        for level in 0..5 {
+            check::<1>(level);
+            check::<2>(level);
+            check::<3>(level);
+            check::<4>(level);
+        }
+        for level in 0..3 {
+            check::<5>(level);
+            check::<6>(level);
+            check::<8>(level);
+        }
+        check::<9>(2);
+        check::<10>(1);
+    }
+
+    #[test]
+    fn axis_order() {
+        const D: usize = 3;
+        let level = 3;
+        let identity = HilbertTransform::<D>::new(level).with_axis_order([0, 1, 2]);
+        let curve = HilbertTransform::<D>::new(level).with_axis_order([2, 0, 1]);
+        assert_eq!(curve.axis_order(), [2, 0, 1]);
+        for key in indices::<D>(level) {
+            let xyz: [usize; D] = key.from_hilbert_index(level);
+            assert_eq!(identity.decode(key), xyz);
+            assert_eq!(identity.encode(&xyz), key);
+
+            // 曲線の k 番目の次元は入力の axis_order[k] 番目の成分
+            let [x, y, z] = curve.decode(key);
+            assert_eq!([z, x, y], xyz);
+            assert_eq!(curve.encode(&[x, y, z]), key);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a permutation")]
+    fn axis_order_not_permutation() {
+        let _ = HilbertTransform::<3>::new(2).with_axis_order([0, 2, 2]);
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+use crate::iter::Walker;
+use crate::{dmap, emap, gray, t_inv};
+
+/// Convert an axis-aligned box into the minimal set of Hilbert index ranges covering it.
+/// 
+/// The box consists of grid points `x` with `min[k] <= x[k] <= max[k]` for every axis `k` (both ends inclusive).
+/// The returned ranges are sorted, disjoint, and merged (no two ranges are contiguous),
+/// and their union is exactly the set of Hilbert indices of the grid points inside the box.
+/// If `min[k] > max[k]` for some axis, the box is empty.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::box_to_ranges;
+/// 
+/// let level = 2;
+/// // the lower-left quadrant [0, 1] x [0, 1] is a single range
+/// assert_eq!( vec![0..4], box_to_ranges([0, 0], [1, 1], level) );
+/// // the bottom row [0, 3] x [0, 0]
+/// assert_eq!( vec![0..2, 14..16], box_to_ranges([0, 0], [3, 0], level) );
+/// ```
+/// 
+pub fn box_to_ranges<const D: usize>(min: [usize; D], max: [usize; D], level: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    if min.iter().zip(max.iter()).all(|(a, b)| a <= b) {
+        subdivide(&min, &max, [0; D], level, 0, 0, 0, &mut ranges);
+    }
+    ranges
+}
+
+/// Get an iterator over the grid points inside an axis-aligned box, in the Hilbert order.
+/// 
+/// The box is given as in `box_to_ranges`, whose ranges are computed up front;
+/// the grid points are then generated lazily as in `points`,
+/// so the memory usage is proportional to the number of ranges, not to the number of points.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::box_points;
+/// 
+/// let level = 2;
+/// let p: Vec<[usize; 2]> = box_points([0, 0], [3, 0], level).collect();
+/// assert_eq!( vec![[0, 0], [1, 0], [2, 0], [3, 0]], p );
+/// ```
+/// 
+pub fn box_points<const D: usize>(min: [usize; D], max: [usize; D], level: usize) -> BoxPoints<D> {
+    BoxPoints {
+        ranges: box_to_ranges(min, max, level).into_iter(),
+        range: 0..0,
+        walker: Walker::new(0, level),
+    }
+}
+
+/// An iterator over grid points inside a box in the Hilbert order, created by `box_points`.
+#[derive(Debug, Clone)]
+pub struct BoxPoints<const D: usize> {
+    ranges: alloc::vec::IntoIter<Range<usize>>,
+    range: Range<usize>,
+    walker: Walker<D>,
+}
+
+impl<const D: usize> Iterator for BoxPoints<D> {
+    type Item = [usize; D];
+
+    fn next(&mut self) -> Option<[usize; D]> {
+        let h = match self.range.next() {
+            Some(h) => h,
+            None => {
+                self.range = self.ranges.next()?;
+                self.range.next()?
+            }
+        };
+        self.walker.seek(h);
+        Some(self.walker.p)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.range.len() + self.ranges.as_slice().iter().map(|r| r.len()).sum::<usize>();
+        (n, Some(n))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for BoxPoints<D> {}
+
+impl<const D: usize> core::iter::FusedIterator for BoxPoints<D> {}
+
+// corner を角とする一辺 2^level の部分格子を, 曲線の順番に再帰的に分割する.
+// prefix はこの部分格子に対応する添字の上位ビット.
+#[allow(clippy::too_many_arguments)]
+fn subdivide<const D: usize>(
+    min: &[usize; D], max: &[usize; D], corner: [usize; D],
+    level: usize, prefix: usize, e: usize, d: usize, ranges: &mut Vec<Range<usize>>,
+) {
+    let side = 1 << level;
+    let mut inside = true;
+    for k in 0..D {
+        let (lo, hi) = (corner[k], corner[k] + side - 1);
+        if hi < min[k] || max[k] < lo { return; }
+        inside &= min[k] <= lo && hi <= max[k];
+    }
+
+    if inside {
+        let range = (prefix << (D*level))..((prefix + 1) << (D*level));
+        match ranges.last_mut() {
+            Some(last) if last.end == range.start => last.end = range.end,
+            _ => ranges.push(range),
+        }
+        return;
+    }
+
+    // 一部だけ重なっている場合 (level > 0 のときに限る)
+    let half = side >> 1;
+    for w in 0..=crate::max::<D>() {
+        let l = t_inv::<D>(gray::encode(w), e, d);
+        let mut child = corner;
+        for (j, x) in child.iter_mut().enumerate() {
+            *x += ((l >> j)&1)*half;
+        }
+        subdivide(
+            min, max, child, level - 1, (prefix << D) | w,
+            e^gray::rotate_left::<D>( emap(w), d+1 ), ( d + dmap::<D>(w) + 1 )%D,
+            ranges,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{box_points, box_to_ranges, indices, FromHilbertIndex, ToHilbertIndex};
+
+    fn check<const D: usize>(min: [usize; D], max: [usize; D], level: usize) {
+        let ranges = box_to_ranges(min, max, level);
+
+        for pair in ranges.windows(2) {
+            assert!(pair[0].start < pair[0].end);
+            assert!(pair[0].end < pair[1].start);
+        }
+
+        let expected: Vec<usize> = indices::<D>(level)
+            .filter(|h| {
+                let p: [usize; D] = h.from_hilbert_index(level);
+                (0..D).all(|k| min[k] <= p[k] && p[k] <= max[k])
+            })
+            .collect();
+        let covered: Vec<usize> = ranges.into_iter().flatten().collect();
+        assert_eq!(expected, covered);
+
+        let it = box_points(min, max, level);
+        assert_eq!(it.len(), expected.len());
+        let p: Vec<usize> = it.map(|p| p.to_hilbert_index(level)).collect();
+        assert_eq!(expected, p);
+    }
+
+    #[test]
+    fn brute_force_2d() {
+        let level = 4;
+        for x0 in 0..16 { for x1 in x0..16 {
+            for y0 in (0..16).step_by(3) { for y1 in (y0..16).step_by(2) {
+                check([x0, y0], [x1, y1], level);
+            }}
+        }}
+    }
+
+    #[test]
+    fn brute_force_3d() {
+        let level = 3;
+        check([0, 0, 0], [7, 7, 7], level);
+        check([1, 2, 3], [5, 6, 4], level);
+        check([3, 3, 3], [3, 3, 3], level);
+        check([0, 4, 0], [3, 7, 3], level);
+    }
+
+    #[test]
+    fn empty_box() {
+        assert!(box_to_ranges([2, 0], [1, 3], 2).is_empty());
+        assert_eq!(box_points([2, 0], [1, 3], 2).next(), None);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{max_level, FromHilbertIndex, ToHilbertIndex};
+
+/// Convert a grid point `[usize; D]` to an index on the reversed Hilbert curve.
+/// 
+/// The reversed curve visits the grid points in the opposite order, i.e. its index is `2.pow(D*level) - 1 - h`
+/// where `h` is the index on the standard curve. It starts at `[2.pow(level) - 1, 0, .., 0]` and ends at the origin.
+/// The Hilbert curve is symmetric under the reflection `x -> 2.pow(level) - 1 - x` of the first axis
+/// combined with the reversal, so the reversed curve is also the mirror image of the standard curve
+/// with respect to the first axis. Adjacent indices give adjacent grid points,
+/// and reversing twice gives the standard curve. The inverse is `from_hilbert_index_reversed`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{to_hilbert_index_reversed, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// assert_eq!( 15, to_hilbert_index_reversed(&[0, 0], level) );
+/// assert_eq!( 0, to_hilbert_index_reversed(&[3, 0], level) );
+/// // the mirror image with respect to the first axis
+/// assert_eq!( [3 - 1, 2].to_hilbert_index(level), to_hilbert_index_reversed(&[1, 2], level) );
+/// ```
+/// 
+pub fn to_hilbert_index_reversed<const D: usize>(p: &[usize; D], level: usize) -> usize {
+    last::<D>(level) - p.to_hilbert_index(level)
+}
+
+/// Convert an index on the reversed Hilbert curve to a grid point `[usize; D]`.
+/// 
+/// This is the inverse of `to_hilbert_index_reversed`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::from_hilbert_index_reversed;
+/// 
+/// let level = 2;
+/// assert_eq!( [3, 0], from_hilbert_index_reversed(0, level) );
+/// assert_eq!( [0, 0], from_hilbert_index_reversed(15, level) );
+/// ```
+/// 
+pub fn from_hilbert_index_reversed<const D: usize>(h: usize, level: usize) -> [usize; D] {
+    (last::<D>(level) - h).from_hilbert_index(level)
+}
+
+// 曲線の最後の添字 2^(D*level) - 1
+#[inline]
+fn last<const D: usize>(level: usize) -> usize {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    crate::iter::last::<D>(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_reversed, indices, to_hilbert_index_reversed, FromHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        let n = 1usize << (D*level);
+        let m = (1 << level) - 1;
+        let mut prv: Option<[usize; D]> = None;
+        for h in indices::<D>(level) {
+            let p: [usize; D] = from_hilbert_index_reversed(h, level);
+            assert_eq!(h, to_hilbert_index_reversed(&p, level));
+
+            // 逆向きの曲線の逆向きは元の曲線
+            let q: [usize; D] = from_hilbert_index_reversed(n - 1 - h, level);
+            assert_eq!(to_hilbert_index_reversed(&q, level), n - 1 - h);
+            assert_eq!(q, h.from_hilbert_index(level));
+
+            // 第 0 成分について鏡像になっている
+            let mut mirror: [usize; D] = h.from_hilbert_index(level);
+            mirror[0] = m - mirror[0];
+            assert_eq!(p, mirror);
+
+            if let Some(prv) = prv {
+                let diff = prv.iter().zip(p.iter())
+                    .map(|(&a, &b)| (a as isize - b as isize).abs())
+                    .sum::<isize>();
+                assert_eq!(diff, 1);
+            }
+            prv = Some(p);
+        }
+    }
+
+    #[test]
+    fn reversed() {
+        for level in 0..5 {
+            check::<2>(level);
+            check::<3>(level);
+        }
+        check::<4>(2);
+        check::<6>(1);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::ToHilbertIndex;
+
+/// The largest Hilbert value (LHV) of `points`, i.e. the largest Hilbert index among them.
+/// 
+/// In a Hilbert R-tree the entries of a node are ordered by the LHV of their data,
+/// which determines where new data is inserted and how overflowing nodes are split.
+/// Returns `0` for an empty slice.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{largest_hilbert_value, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let points = [[1, 2], [6, 1], [3, 3]];
+/// assert_eq!( [6, 1].to_hilbert_index(level), largest_hilbert_value(&points, level) );
+/// ```
+/// 
+pub fn largest_hilbert_value<const D: usize>(points: &[[usize; D]], level: usize) -> usize {
+    points.iter()
+        .map(|p| p.to_hilbert_index(level))
+        .max()
+        .unwrap_or(0)
+}
+
+/// The minimum bounding rectangle (MBR) of `points`, as the lower and upper corners `(min, max)`.
+/// 
+/// Both corners are inclusive, i.e. every point `p` satisfies `min[k] <= p[k] <= max[k]`,
+/// and each bound is attained by some point. The MBRs of child nodes are merged
+/// by applying this function to their corners.
+/// 
+/// # Panics
+/// 
+/// Panics if `points` is empty.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::mbr;
+/// 
+/// assert_eq!( ([1, 1], [6, 3]), mbr(&[[1, 2], [6, 1], [3, 3]]) );
+/// ```
+/// 
+pub fn mbr<const D: usize>(points: &[[usize; D]]) -> ([usize; D], [usize; D]) {
+    let (first, rest) = points.split_first().expect("mbr: no points are given");
+    rest.iter().fold((*first, *first), |(mut min, mut max), p| {
+        for k in 0..D {
+            min[k] = min[k].min(p[k]);
+            max[k] = max[k].max(p[k]);
+        }
+        (min, max)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{largest_hilbert_value, mbr, points, ToHilbertIndex};
+
+    #[test]
+    fn lhv() {
+        let level = 3;
+        let all: Vec<[usize; 3]> = points(level).collect();
+        for chunk in all.chunks(37) {
+            let lhv = largest_hilbert_value(chunk, level);
+            assert!(chunk.iter().all(|p| p.to_hilbert_index(level) <= lhv));
+            assert!(chunk.iter().any(|p| p.to_hilbert_index(level) == lhv));
+        }
+        assert_eq!(largest_hilbert_value(&all, level), 511);
+        assert_eq!(largest_hilbert_value::<3>(&[], level), 0);
+    }
+
+    #[test]
+    fn bounding() {
+        let level = 3;
+        let all: Vec<[usize; 2]> = points(level).collect();
+        for chunk in all.chunks(7) {
+            let (min, max) = mbr(chunk);
+            for k in 0..2 {
+                assert!(chunk.iter().all(|p| min[k] <= p[k] && p[k] <= max[k]));
+                // 境界は点で実現される
+                assert!(chunk.iter().any(|p| p[k] == min[k]));
+                assert!(chunk.iter().any(|p| p[k] == max[k]));
+            }
+        }
+        assert_eq!(mbr(&[[4, 2, 9]]), ([4, 2, 9], [4, 2, 9]));
+
+        // MBR の併合
+        let (a, b) = (mbr(&[[0, 5], [2, 3]]), mbr(&[[4, 4], [1, 7]]));
+        assert_eq!(mbr(&[a.0, a.1, b.0, b.1]), ([0, 3], [4, 7]));
+    }
+
+    #[test]
+    #[should_panic(expected = "no points are given")]
+    fn empty() {
+        mbr::<2>(&[]);
+    }
+}
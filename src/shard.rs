@@ -0,0 +1,105 @@
+use core::ops::Range;
+use crate::{num_cells, ToHilbertIndex};
+
+/// The shard of a grid point when the Hilbert curve is split into `num_shards` contiguous blocks.
+/// 
+/// The shard is `h * num_shards / 2.pow(D*level)` (computed without overflow), where `h` is the Hilbert index of `p`,
+/// so that each shard is a contiguous range of indices given by `shard_range`
+/// and thus a spatially compact region of the grid.
+/// The numbers of cells of the shards differ by at most one.
+/// This is the standard Hilbert-based partitioning for distributing spatial work.
+/// 
+/// # Panics
+/// 
+/// Panics if `num_shards == 0`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{shard_of, shard_range, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// let p = [3, 1];
+/// assert_eq!( 3, shard_of(&p, level, 4) );
+/// assert!( shard_range::<2>(3, level, 4).contains(&p.to_hilbert_index(level)) );
+/// ```
+/// 
+pub fn shard_of<const D: usize>(p: &[usize; D], level: usize, num_shards: usize) -> usize {
+    assert!(num_shards > 0, "the number of shards must be positive");
+    let h = p.to_hilbert_index(level) as u128;
+    (h * num_shards as u128 / num_cells::<D>(level) as u128) as usize
+}
+
+/// The range of Hilbert indices of a shard, i.e. the indices `h` with `h * num_shards / 2.pow(D*level) == shard`.
+/// 
+/// The ranges of the shards `0..num_shards` are disjoint and cover the whole curve `0..2.pow(D*level)` in order.
+/// A shard may be empty if `num_shards > 2.pow(D*level)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `num_shards == 0` or `shard >= num_shards`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::shard_range;
+/// 
+/// let level = 1;
+/// assert_eq!( 0..2, shard_range::<2>(0, level, 3) );
+/// assert_eq!( 2..3, shard_range::<2>(1, level, 3) );
+/// assert_eq!( 3..4, shard_range::<2>(2, level, 3) );
+/// ```
+/// 
+pub fn shard_range<const D: usize>(shard: usize, level: usize, num_shards: usize) -> Range<usize> {
+    assert!(num_shards > 0, "the number of shards must be positive");
+    assert!(shard < num_shards, "shard {} is out of 0..{}", shard, num_shards);
+    let (total, n) = (num_cells::<D>(level) as u128, num_shards as u128);
+    // h*n/total >= s となる最小の h は ceil(s*total/n)
+    let start = |s: u128| (s * total).div_ceil(n) as usize;
+    start(shard as u128)..start(shard as u128 + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{points, shard_of, shard_range, ToHilbertIndex};
+
+    fn check<const D: usize>(level: usize, num_shards: usize) {
+        let mut end = 0;
+        for shard in 0..num_shards {
+            let range = shard_range::<D>(shard, level, num_shards);
+            assert_eq!(range.start, end);
+            end = range.end;
+        }
+        assert_eq!(end, 1 << (D*level));
+
+        for p in points::<D>(level) {
+            let shard = shard_of(&p, level, num_shards);
+            assert!(shard < num_shards);
+            assert!(shard_range::<D>(shard, level, num_shards).contains(&p.to_hilbert_index(level)));
+        }
+    }
+
+    #[test]
+    fn contiguous_cover() {
+        for num_shards in 1..20 {
+            check::<2>(2, num_shards);
+            check::<3>(1, num_shards);
+        }
+        check::<2>(3, 7);
+        check::<3>(2, 64);
+    }
+
+    #[test]
+    fn balanced() {
+        let sizes: Vec<usize> = (0..5).map(|s| shard_range::<2>(s, 3, 5).len()).collect();
+        assert_eq!(sizes, vec![13, 13, 13, 13, 12]);
+    }
+
+    #[test]
+    fn at_max_level() {
+        let level = crate::max_level::<2>();
+        let p = [(1 << level) - 1, 0];
+        assert_eq!(shard_of(&p, level, 10), 9);
+        assert_eq!(shard_range::<2>(9, level, 10).end, 1 << (2*level));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{FromHilbertIndex, HilbertError, ToHilbertIndex};
+
+// 原点中心の座標に加えるずれ 2^(level-1)
+#[inline]
+fn bias(level: usize) -> usize {
+    debug_assert!(level <= 8*core::mem::size_of::<usize>(), "level {} is too large for isize coordinates", level);
+    if level == 0 { 0 } else { 1 << (level - 1) }
+}
+
+// 原点中心の座標を 0..2^level の格子点に移す
+#[inline]
+fn shift<const D: usize>(p: &[isize; D], level: usize) -> [usize; D] {
+    let mut q = [0; D];
+    for (q, &x) in q.iter_mut().zip(p.iter()) {
+        *q = (x as usize).wrapping_add(bias(level));
+    }
+    q
+}
+
+/// Convert a grid point centered at the origin `[isize; D]` to `usize`.
+/// 
+/// With a given `level`, each component `x` must satisfy `-2.pow(level-1) <= x < 2.pow(level-1)`,
+/// and it is shifted by `2.pow(level-1)` to the unsigned grid `0..2.pow(level)` before conversion.
+/// For `level == 0`, the only grid point is `[0; D]`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{ToCenteredHilbertIndex, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// assert_eq!( [0, 0].to_hilbert_index(level), [-4, -4].to_centered_hilbert_index(level) );
+/// assert_eq!( [4, 4].to_hilbert_index(level), [0, 0].to_centered_hilbert_index(level) );
+/// ```
+/// 
+pub trait ToCenteredHilbertIndex<const D: usize> {
+    /// Convert a grid point `[isize; D]` centered at the origin to a Hilbert index `usize`.
+    /// 
+    /// This is equal to `to_hilbert_index` of the shifted grid point.
+    fn to_centered_hilbert_index(&self, level: usize) -> usize;
+
+    /// Checked version of `to_centered_hilbert_index`.
+    /// 
+    /// Returns `Err(HilbertError::LevelOutOfRange { .. })` if `level > max_level::<D>()`,
+    /// and `Err(HilbertError::SignedOutOfRange { .. })` if some component `x` does not satisfy
+    /// `-2.pow(level-1) <= x < 2.pow(level-1)`.
+    /// 
+    /// ```
+    /// use hilbert_index::{HilbertError, ToCenteredHilbertIndex};
+    /// 
+    /// assert_eq!( Ok(0), [-1, -1].try_to_centered_hilbert_index(1) );
+    /// assert_eq!(
+    ///     Err(HilbertError::SignedOutOfRange { dim: 0, value: 1, min: -1, max: 1 }),
+    ///     [1, 0].try_to_centered_hilbert_index(1),
+    /// );
+    /// ```
+    /// 
+    fn try_to_centered_hilbert_index(&self, level: usize) -> Result<usize, HilbertError>;
+}
+
+/// Convert `usize` to a grid point centered at the origin `[isize; D]`.
+/// 
+/// This is the inverse of `ToCenteredHilbertIndex`;
+/// the components lie in `-2.pow(level-1)..2.pow(level-1)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{FromCenteredHilbertIndex, ToCenteredHilbertIndex};
+/// 
+/// let level = 3;
+/// assert_eq!( [-4, -4], 0.from_centered_hilbert_index(level) );
+/// 
+/// let h = [-1, 3].to_centered_hilbert_index(level);
+/// assert_eq!( [-1, 3], h.from_centered_hilbert_index(level) );
+/// ```
+/// 
+#[allow(clippy::wrong_self_convention)]
+pub trait FromCenteredHilbertIndex<const D: usize> {
+    /// Convert a Hilbert index `usize` to a grid point `[isize; D]` centered at the origin.
+    fn from_centered_hilbert_index(&self, level: usize) -> [isize; D];
+}
+
+impl<const D: usize> ToCenteredHilbertIndex::<D> for [isize; D] {
+    fn to_centered_hilbert_index(&self, level: usize) -> usize {
+        shift(self, level).to_hilbert_index(level)
+    }
+
+    fn try_to_centered_hilbert_index(&self, level: usize) -> Result<usize, HilbertError> {
+        crate::level::check_level::<D>(level)?;
+        let min = -(bias(level) as isize);
+        let max = ((1usize << level) - bias(level)) as isize;
+        for (dim, &value) in self.iter().enumerate() {
+            if value < min || value >= max {
+                return Err(HilbertError::SignedOutOfRange { dim, value, min, max });
+            }
+        }
+
+        Ok(self.to_centered_hilbert_index(level))
+    }
+}
+
+impl<const D: usize> FromCenteredHilbertIndex::<D> for usize {
+    fn from_centered_hilbert_index(&self, level: usize) -> [isize; D] {
+        let p: [usize; D] = self.from_hilbert_index(level);
+        let mut q = [0; D];
+        for (q, &x) in q.iter_mut().zip(p.iter()) {
+            *q = x.wrapping_sub(bias(level)) as isize;
+        }
+        q
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{indices, FromCenteredHilbertIndex, FromHilbertIndex, HilbertError, ToCenteredHilbertIndex, ToHilbertIndex};
+
+    #[test]
+    fn same_as_unsigned() {
+        const D: usize = 2;
+        let level = 3;
+        assert_eq!([-4, -4].to_centered_hilbert_index(level), [0, 0].to_hilbert_index(level));
+
+        for key in indices::<D>(level) {
+            let p: [usize; D] = key.from_hilbert_index(level);
+            let q: [isize; D] = key.from_centered_hilbert_index(level);
+            for (&x, &y) in p.iter().zip(q.iter()) {
+                assert_eq!(x as isize - 4, y);
+            }
+            assert_eq!(q.to_centered_hilbert_index(level), key);
+            assert_eq!(q.try_to_centered_hilbert_index(level), Ok(key));
+        }
+    }
+
+    #[test]
+    fn centered_range() {
+        assert_eq!([0, 0, 0].try_to_centered_hilbert_index(0), Ok(0));
+        assert_eq!(
+            [-1, 0, 0].try_to_centered_hilbert_index(0),
+            Err(HilbertError::SignedOutOfRange { dim: 0, value: -1, min: 0, max: 1 }),
+        );
+        assert_eq!(
+            [0, 4].try_to_centered_hilbert_index(3),
+            Err(HilbertError::SignedOutOfRange { dim: 1, value: 4, min: -4, max: 4 }),
+        );
+        assert_eq!(
+            [-5, 0].try_to_centered_hilbert_index(3),
+            Err(HilbertError::SignedOutOfRange { dim: 0, value: -5, min: -4, max: 4 }),
+        );
+    }
+
+    #[test]
+    fn centered_level() {
+        let level = crate::max_level::<1>();
+        let min = -(1 << (level - 1));
+        assert_eq!([min].try_to_centered_hilbert_index(level), Ok(0));
+        assert_eq!(
+            [0].try_to_centered_hilbert_index(level + 1),
+            Err(HilbertError::LevelOutOfRange { level: level + 1, max_level: level }),
+        );
+        assert_eq!(
+            [0, 0].try_to_centered_hilbert_index(usize::BITS as usize),
+            Err(HilbertError::LevelOutOfRange { level: usize::BITS as usize, max_level: crate::max_level::<2>() }),
+        );
+    }
+}
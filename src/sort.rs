@@ -0,0 +1,104 @@
+use crate::{max_level, ToHilbertIndex};
+
+/// Sort `items` in the Hilbert order of the grid points given by `key`.
+/// 
+/// The Hilbert index of each item is computed only once, and the items are sorted indirectly
+/// by `slice::sort_by_cached_key`, so this allocates `O(items.len())` memory.
+/// The sort is stable: items with the same grid point keep their relative order.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::hilbert_sort_by_key;
+/// 
+/// let mut items = vec![("a", [1, 0]), ("b", [0, 0]), ("c", [1, 1]), ("d", [0, 1])];
+/// hilbert_sort_by_key(&mut items, 1, |item| item.1);
+/// assert_eq!( vec!["b", "d", "c", "a"], items.iter().map(|item| item.0).collect::<Vec<_>>() );
+/// ```
+/// 
+pub fn hilbert_sort_by_key<T, const D: usize>(items: &mut [T], level: usize, key: impl Fn(&T) -> [usize; D]) {
+    assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    items.sort_by_cached_key(|item| key(item).to_hilbert_index(level));
+}
+
+/// Sort grid points in the Hilbert order.
+/// 
+/// This is equivalent to `hilbert_sort_by_key(points, level, |p| *p)`.
+/// 
+/// # Panics
+/// 
+/// Panics if `level > max_level::<D>()`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::hilbert_sort;
+/// 
+/// let mut points = [[1, 0], [0, 0], [1, 1], [0, 1]];
+/// hilbert_sort(&mut points, 1);
+/// assert_eq!( [[0, 0], [0, 1], [1, 1], [1, 0]], points );
+/// ```
+/// 
+pub fn hilbert_sort<const D: usize>(points: &mut [[usize; D]], level: usize) {
+    hilbert_sort_by_key(points, level, |p| *p);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hilbert_sort, hilbert_sort_by_key, points, ToHilbertIndex};
+
+    // 線形合同法による疑似乱数
+    fn shuffle<T>(items: &mut [T]) {
+        let mut seed: u64 = 2021;
+        for i in (1..items.len()).rev() {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            items.swap(i, (seed >> 33) as usize % (i + 1));
+        }
+    }
+
+    #[test]
+    fn sorted_points_are_adjacent() {
+        const D: usize = 3;
+        let level = 3;
+        let mut p: Vec<[usize; D]> = points(level).collect();
+        shuffle(&mut p);
+        hilbert_sort(&mut p, level);
+
+        // 全ての格子点を並べ替えると曲線そのものになる
+        assert_eq!(p, points::<D>(level).collect::<Vec<_>>());
+        for w in p.windows(2) {
+            assert!(w[0].to_hilbert_index(level) < w[1].to_hilbert_index(level));
+            let diff = w[0].iter().zip(w[1].iter())
+                .map(|(&a, &b)| (a as isize - b as isize).abs())
+                .sum::<isize>();
+            assert_eq!(diff, 1);
+        }
+    }
+
+    #[test]
+    fn sort_payload() {
+        const D: usize = 2;
+        let level = 4;
+        let mut items: Vec<(usize, [usize; D])> = points(level).step_by(3)
+            .chain(points(level).step_by(5))
+            .enumerate()
+            .collect();
+        shuffle(&mut items);
+        hilbert_sort_by_key(&mut items, level, |item| item.1);
+
+        let hindices: Vec<usize> = items.iter().map(|item| item.1.to_hilbert_index(level)).collect();
+        assert!(hindices.windows(2).all(|w| w[0] <= w[1]));
+
+        // 大半は隣接する格子点への移動になっている
+        let near = items.windows(2)
+            .filter(|w| w[0].1.iter().zip(w[1].1.iter())
+                .map(|(&a, &b)| (a as isize - b as isize).abs())
+                .sum::<isize>() <= 3)
+            .count();
+        assert!(2*near > items.len());
+    }
+}
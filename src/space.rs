@@ -0,0 +1,82 @@
+use crate::{axis_extent, num_cells, FromHilbertIndex, ToHilbertIndex};
+
+/// The grid box of the `D`-dimensional Hilbert curve of level `L`, both given as types.
+/// 
+/// The sizes are available as associated constants,
+/// so that containers over the whole box can be sized at compile time,
+/// and `encode` and `decode` take no `level` argument.
+/// Using the constants with `D*L >= usize::BITS` is rejected at compile time.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertSpace;
+/// 
+/// type Space = HilbertSpace<2, 3>;
+/// 
+/// let mut cells = [0u8; Space::NUM_CELLS];
+/// cells[Space::encode(&[1, 2])] = 1;
+/// assert_eq!( 64, cells.len() );
+/// assert_eq!( 8, Space::AXIS_EXTENT );
+/// assert_eq!( [1, 2], Space::decode(Space::encode(&[1, 2])) );
+/// ```
+/// 
+/// ```compile_fail
+/// use hilbert_index::HilbertSpace;
+/// 
+/// // 2.pow(2*64) overflows usize
+/// let n = HilbertSpace::<2, 64>::NUM_CELLS;
+/// ```
+/// 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HilbertSpace<const D: usize, const L: usize>;
+
+impl<const D: usize, const L: usize> HilbertSpace<D, L> {
+    /// The level `L` of the curve.
+    pub const LEVEL: usize = L;
+
+    /// The number of grid points `2.pow(D*L)`, i.e. `num_cells::<D>(L)`.
+    pub const NUM_CELLS: usize = num_cells::<D>(L);
+
+    /// The number of grid points along each axis `2.pow(L)`, i.e. `axis_extent(L)`.
+    pub const AXIS_EXTENT: usize = axis_extent(L);
+
+    /// Convert a grid point to a Hilbert index, i.e. `p.to_hilbert_index(L)`.
+    #[inline]
+    pub fn encode(p: &[usize; D]) -> usize {
+        p.to_hilbert_index(L)
+    }
+
+    /// Convert a Hilbert index to a grid point, i.e. `h.from_hilbert_index(L)`.
+    #[inline]
+    pub fn decode(h: usize) -> [usize; D] {
+        h.from_hilbert_index(L)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{axis_extent, indices, num_cells, FromHilbertIndex, HilbertSpace, ToHilbertIndex};
+
+    fn check<const D: usize, const L: usize>() {
+        type S<const D: usize, const L: usize> = HilbertSpace<D, L>;
+        assert_eq!(S::<D, L>::LEVEL, L);
+        assert_eq!(S::<D, L>::NUM_CELLS, num_cells::<D>(L));
+        assert_eq!(S::<D, L>::AXIS_EXTENT, axis_extent(L));
+        for h in indices::<D>(L) {
+            let p: [usize; D] = h.from_hilbert_index(L);
+            assert_eq!(S::<D, L>::decode(h), p);
+            assert_eq!(S::<D, L>::encode(&p), p.to_hilbert_index(L));
+        }
+    }
+
+    #[test]
+    fn same_as_runtime() {
+        check::<1, 5>();
+        check::<2, 0>();
+        check::<2, 3>();
+        check::<3, 2>();
+        check::<4, 2>();
+        assert_eq!(HilbertSpace::<2, 31>::NUM_CELLS, 1 << 62);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{from_words_state, max_level, to_words_state};
+
+/// Convert a Hilbert index to a grid point, and return the state `(e, d)` of the curve after the last level.
+/// 
+/// The state is the entry vertex `e` and the direction `d` of the curve inside the cell of the grid point,
+/// in the same convention as the seed `(e0, d0)` of `from_hilbert_index_with`.
+/// Hence the cell can be refined by another curve seeded with `(e, d)`:
+/// decoding `hf` at `m` levels with this seed and appending its bits to the grid point
+/// gives the grid point of `(h << (D*m)) | hf` at `level + m`.
+/// This allows stitching sub-curves at block boundaries, e.g. when blocks are processed independently.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{decode_with_state, FromHilbertIndex};
+/// 
+/// let (level, m) = (2, 3);
+/// let h = 9;
+/// let (p, e, d) = decode_with_state::<2>(h, level);
+/// let q: [usize; 2] = 5.from_hilbert_index_with(m, e, d);
+/// let r: [usize; 2] = ((h << (2*m)) | 5).from_hilbert_index(level + m);
+/// assert_eq!( r, [(p[0] << m) | q[0], (p[1] << m) | q[1]] );
+/// ```
+/// 
+pub fn decode_with_state<const D: usize>(h: usize, level: usize) -> ([usize; D], usize, usize) {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    from_words_state(&h, level, 0, 0)
+}
+
+/// Convert a grid point to a Hilbert index along the curve seeded with `(e0, d0)`,
+/// and return the state `(e, d)` of the curve after the last level.
+/// 
+/// The index is the same as `to_hilbert_index_with(level, e0, d0)`,
+/// and the state is the seed for the sub-curve inside the cell of the grid point, as in `decode_with_state`.
+/// With `(e0, d0) = (0, 0)` it is the encoder corresponding to `decode_with_state`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{decode_with_state, encode_with_state};
+/// 
+/// let level = 3;
+/// let (p, e, d) = decode_with_state::<3>(100, level);
+/// assert_eq!( (100, e, d), encode_with_state(&p, level, 0, 0) );
+/// ```
+/// 
+pub fn encode_with_state<const D: usize>(p: &[usize; D], level: usize, e0: usize, d0: usize) -> (usize, usize, usize) {
+    debug_assert!(level <= max_level::<D>(), "level {} exceeds max_level::<{}>() = {}", level, D, max_level::<D>());
+    to_words_state(p, level, e0, d0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode_with_state, encode_with_state, indices, FromHilbertIndex, ToHilbertIndex};
+
+    // level の曲線の各セルを m level の部分曲線で埋めて繋げる
+    fn stitch<const D: usize>(level: usize, m: usize) {
+        let mut prv: Option<[usize; D]> = None;
+        for hc in indices::<D>(level) {
+            let (pc, e, d) = decode_with_state::<D>(hc, level);
+            assert_eq!(encode_with_state(&pc, level, 0, 0), (hc, e, d));
+
+            for hf in indices::<D>(m) {
+                let pf: [usize; D] = hf.from_hilbert_index_with(m, e, d);
+                let mut p = [0; D];
+                for k in 0..D {
+                    p[k] = (pc[k] << m) | pf[k];
+                }
+                assert_eq!(p, ((hc << (D*m)) | hf).from_hilbert_index(level + m));
+                assert_eq!(encode_with_state(&pf, m, e, d).0, hf);
+
+                if let Some(prv) = prv {
+                    let diff = prv.iter().zip(p.iter())
+                        .map(|(&a, &b)| (a as isize - b as isize).abs())
+                        .sum::<isize>();
+                    assert_eq!(diff, 1);
+                }
+                prv = Some(p);
+            }
+        }
+    }
+
+    #[test]
+    fn stitched_curve() {
+        for level in 0..3 {
+            for m in 0..3 {
+                stitch::<2>(level, m);
+                stitch::<3>(level, m);
+            }
+        }
+        stitch::<4>(1, 2);
+        stitch::<5>(2, 1);
+    }
+
+    #[test]
+    fn same_as_seeded() {
+        let level = 3;
+        for e0 in 0..8 {
+            for d0 in 0..3 {
+                for p in [[0, 0, 0], [1, 7, 3], [6, 2, 5]] {
+                    let (h, _, _) = encode_with_state(&p, level, e0, d0);
+                    assert_eq!(h, p.to_hilbert_index_with(level, e0, d0));
+                }
+            }
+        }
+    }
+}
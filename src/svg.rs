@@ -0,0 +1,73 @@
+use alloc::string::String;
+use core::fmt::Write;
+use crate::points;
+
+/// Trace the 2D Hilbert curve of a given level as an SVG `<path>` element (requires the `alloc` feature).
+/// 
+/// The path visits the centers of all `2.pow(2*level)` cells in the Hilbert order,
+/// where the cell of a grid point `[x, y]` is the square of `size` pixels at `(x*size, y*size)`.
+/// Note that the `y` axis of SVG points downward.
+/// This is meant for visual inspection; see `svg_path_from_points` for other orders.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::to_svg_path;
+/// 
+/// assert_eq!(
+///     r#"<path d="M 5 5 L 5 15 L 15 15 L 15 5" fill="none" stroke="black"/>"#,
+///     to_svg_path(1, 10.0),
+/// );
+/// ```
+/// 
+pub fn to_svg_path(level: usize, size: f64) -> String {
+    svg_path_from_points(points::<2>(level), size)
+}
+
+/// Trace 2D grid points in the given order as an SVG `<path>` element (requires the `alloc` feature).
+/// 
+/// The coordinates are scaled as in `to_svg_path`.
+/// This is useful to see the variants of the curve, e.g. with
+/// `indices::<2>(level).map(|h| from_hilbert_index_reversed(h, level))`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_reversed, indices, svg_path_from_points};
+/// 
+/// let level = 1;
+/// let path = svg_path_from_points(indices::<2>(level).map(|h| from_hilbert_index_reversed(h, level)), 2.0);
+/// assert_eq!( r#"<path d="M 3 1 L 3 3 L 1 3 L 1 1" fill="none" stroke="black"/>"#, path );
+/// ```
+/// 
+pub fn svg_path_from_points<I: IntoIterator<Item = [usize; 2]>>(points: I, size: f64) -> String {
+    let mut s = String::from(r#"<path d=""#);
+    for (k, [x, y]) in points.into_iter().enumerate() {
+        if k > 0 { s.push(' '); }
+        // String への書き込みは失敗しない
+        write!(s, "{} {} {}", if k == 0 { 'M' } else { 'L' },
+            (x as f64 + 0.5)*size, (y as f64 + 0.5)*size).unwrap();
+    }
+    s.push_str(r#"" fill="none" stroke="black"/>"#);
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{svg_path_from_points, to_svg_path};
+
+    #[test]
+    fn number_of_commands() {
+        for level in 0..5 {
+            let path = to_svg_path(level, 4.0);
+            assert_eq!(path.matches('M').count(), 1);
+            assert_eq!(path.matches('L').count(), (1 << (2*level)) - 1);
+        }
+        assert_eq!(to_svg_path(0, 1.0), r#"<path d="M 0.5 0.5" fill="none" stroke="black"/>"#);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(svg_path_from_points(None, 1.0), r#"<path d="" fill="none" stroke="black"/>"#);
+    }
+}
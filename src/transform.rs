@@ -0,0 +1,88 @@
+//! The per-level transform of the Hilbert curve, for building other curves on top of this crate.
+//! 
+//! Following Hamilton's report, every cell visited by the curve is characterized by
+//! its entry vertex `e` (a `D`-bit word whose `k`-th bit is the `k`-th coordinate of the corner where the curve enters the cell)
+//! and its intra direction `d` (the axis along which the curve leaves the cell, i.e. the exit vertex is `e ^ (1 << d)`).
+//! `t` maps a `D`-bit word of the cell (e.g. a bit plane of a point, see `extract_bit_plane`) into the standard frame,
+//! in which the curve enters at `0` and leaves at `1 << (D-1)`,
+//! so that the sub-cell index is obtained by `gray::decode`. `t_inv` is the inverse.
+//! 
+//! Both directions of the conversion in this crate are built from these functions.
+//! Each step of the encoder is, in terms of the functions of this crate,
+//! 
+//! ```text
+//! w  = gray::decode::<D>(t::<D>(extract_bit_plane(p, i), e, d))
+//! e ^= gray::rotate_left::<D>(entry of the w-th sub-cell, d+1)
+//! d  = (d + intra direction of the w-th sub-cell + 1) % D
+//! h  = (h << D) | w
+//! ```
+//! 
+//! and the decoder reverses it with `t_inv::<D>(gray::encode(w), e, d)`.
+//! The states `(e, d)` themselves are available through `encode_with_state` and `decode_with_state`.
+
+use crate::gray;
+
+/// Transform a `D`-bit word `b` of the cell with the entry vertex `e` and the intra direction `d` into the standard frame.
+/// 
+/// This is `(b ^ e)` rotated right by `d+1` within `D` bits, so that `e` goes to `0` and the exit vertex `e ^ (1 << d)` goes to `1 << (D-1)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::transform::{t, t_inv};
+/// 
+/// for e in 0..8 {
+///     for d in 0..3 {
+///         assert_eq!( 0, t::<3>(e, e, d) );
+///         assert_eq!( 0b100, t::<3>(e ^ (1 << d), e, d) );
+///         for b in 0..8 {
+///             assert_eq!( b, t_inv::<3>(t::<3>(b, e, d), e, d) );
+///         }
+///     }
+/// }
+/// ```
+/// 
+#[inline]
+pub const fn t<const D: usize>(b: usize, e: usize, d: usize) -> usize { gray::rotate_right::<D>(b^e, d+1) }
+
+/// The inverse of `t`, from the standard frame back to the cell with the entry vertex `e` and the intra direction `d`.
+/// 
+/// This is `b` rotated left by `d+1` within `D` bits, followed by `^ e`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::transform::{t, t_inv};
+/// 
+/// assert_eq!( 0b101, t_inv::<3>(0, 0b101, 1) );
+/// for b in 0..4 {
+///     assert_eq!( b, t::<2>(t_inv::<2>(b, 0b10, 0), 0b10, 0) );
+/// }
+/// ```
+/// 
+#[inline]
+pub const fn t_inv<const D: usize>(b: usize, e: usize, d: usize) -> usize { gray::rotate_left::<D>(b, d+1)^e }
+
+#[cfg(test)]
+mod tests {
+    use super::{t, t_inv};
+    use crate::{decode_with_state, extract_bit_plane, gray, indices, max, FromHilbertIndex};
+
+    fn check<const D: usize>(level: usize) {
+        // 親の格子の状態 (e, d) で最下位のビット面を変換すると, 添字の最下位の語が得られる
+        for h in indices::<D>(level) {
+            let (_, e, d) = decode_with_state::<D>(h >> D, level - 1);
+            let p: [usize; D] = h.from_hilbert_index(level);
+            let (b, w) = (extract_bit_plane(&p, 0), h & max::<D>());
+            assert_eq!(gray::decode::<D>(t::<D>(b, e, d)), w);
+            assert_eq!(t_inv::<D>(gray::encode(w), e, d), b);
+        }
+    }
+
+    #[test]
+    fn last_word() {
+        check::<2>(3);
+        check::<3>(2);
+        check::<4>(2);
+    }
+}
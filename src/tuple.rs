@@ -0,0 +1,84 @@
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+// タプルを配列に変換すれば, 残りは trait の既定の実装が与える
+macro_rules! impl_to_hilbert_index {
+    ($d:expr, ($($t:ident),*), ($($x:ident),*)) => {
+        impl ToHilbertIndex<$d> for ($($t,)*) {
+            #[inline]
+            fn to_grid_point(&self) -> [usize; $d] {
+                let &($($x,)*) = self;
+                [$($x),*]
+            }
+        }
+    };
+}
+
+impl_to_hilbert_index!(2, (usize, usize), (x, y));
+impl_to_hilbert_index!(3, (usize, usize, usize), (x, y, z));
+
+/// Convert a Hilbert index `usize` to a 2D grid point `(x, y)`.
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index` for `(usize, usize)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_tuple2, ToHilbertIndex};
+/// 
+/// let level = 3;
+/// let h = (3usize, 5usize).to_hilbert_index(level);
+/// assert_eq!( [3, 5].to_hilbert_index(level), h );
+/// assert_eq!( (3, 5), from_hilbert_index_tuple2(h, level) );
+/// ```
+/// 
+pub fn from_hilbert_index_tuple2(h: usize, level: usize) -> (usize, usize) {
+    let [x, y] = h.from_hilbert_index(level);
+    (x, y)
+}
+
+/// Convert a Hilbert index `usize` to a 3D grid point `(x, y, z)`.
+/// 
+/// This is the inverse of `ToHilbertIndex::to_hilbert_index` for `(usize, usize, usize)`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_hilbert_index_tuple3, ToHilbertIndex};
+/// 
+/// let level = 4;
+/// let h = (3usize, 5usize, 9usize).to_hilbert_index(level);
+/// assert_eq!( [3, 5, 9].to_hilbert_index(level), h );
+/// assert_eq!( (3, 5, 9), from_hilbert_index_tuple3(h, level) );
+/// ```
+/// 
+pub fn from_hilbert_index_tuple3(h: usize, level: usize) -> (usize, usize, usize) {
+    let [x, y, z] = h.from_hilbert_index(level);
+    (x, y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_hilbert_index_tuple2, from_hilbert_index_tuple3, indices, FromHilbertIndex, HilbertError, ToHilbertIndex};
+
+    #[test]
+    fn same_as_array() {
+        let level = 3;
+        for h in indices::<2>(level) {
+            let [x, y] = h.from_hilbert_index(level);
+            assert_eq!(from_hilbert_index_tuple2(h, level), (x, y));
+            assert_eq!((x, y).to_hilbert_index(level), h);
+            assert_eq!((x, y).to_compact_hilbert_index([level, level]), h);
+        }
+        for h in indices::<3>(level) {
+            let [x, y, z] = h.from_hilbert_index(level);
+            assert_eq!(from_hilbert_index_tuple3(h, level), (x, y, z));
+            assert_eq!((x, y, z).to_hilbert_index(level), h);
+            assert_eq!((x, y, z).to_hilbert_index_as::<u16>(level) as usize, h);
+            assert_eq!((x, y, z).to_hilbert_index_with(level, 0b101, 1), [x, y, z].to_hilbert_index_with(level, 0b101, 1));
+        }
+        assert_eq!(
+            (1usize, 8usize).try_to_hilbert_index(3),
+            Err(HilbertError::OutOfRange { dim: 1, value: 8, bound: 8 }),
+        );
+    }
+}
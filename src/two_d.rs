@@ -0,0 +1,87 @@
+//! 2D Hilbert curve with the `xy2d` / `d2xy` naming common in 2D libraries.
+//! 
+//! These functions ease migration from 2D-only crates such as `hilbert_2d`,
+//! and from code following the [Wikipedia article](https://en.wikipedia.org/wiki/Hilbert_curve).
+//! They are thin wrappers of the generic `D = 2` conversions:
+//! 
+//! | this module          | generic API                            |
+//! |----------------------|----------------------------------------|
+//! | `xy2d(x, y, level)`  | `[x, y].to_hilbert_index(level)`       |
+//! | `d2xy(d, level)`     | `d.from_hilbert_index(level)` as tuple |
+//! 
+//! Note that the size of the grid is given by the `level` (the order of the curve),
+//! not by the side length `n = 2.pow(level)` as in the Wikipedia article.
+//! The curve is the same as the one in the article: it starts at `(0, 0)` and ends at `(n - 1, 0)`.
+//! 
+//! ```
+//! use hilbert_index::two_d::{d2xy, xy2d};
+//! 
+//! let level = 2;
+//! assert_eq!( 7, xy2d(1, 2, level) );
+//! assert_eq!( (1, 2), d2xy(7, level) );
+//! ```
+
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// Convert a grid point `(x, y)` to a Hilbert index `d`, where `x, y < 2.pow(level)`.
+#[inline]
+pub fn xy2d(x: usize, y: usize, level: usize) -> usize {
+    [x, y].to_hilbert_index(level)
+}
+
+/// Convert a Hilbert index `d` to a grid point `(x, y)`, where `d < 4.pow(level)`. This is the inverse of `xy2d`.
+#[inline]
+pub fn d2xy(d: usize, level: usize) -> (usize, usize) {
+    let [x, y] = d.from_hilbert_index(level);
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{d2xy, xy2d};
+
+    // Wikipedia の d2xy をそのまま移植したもの. n は一辺の長さ.
+    fn wikipedia_d2xy(n: usize, d: usize) -> (usize, usize) {
+        let (mut x, mut y, mut t, mut s) = (0, 0, d, 1);
+        while s < n {
+            let rx = 1 & (t/2);
+            let ry = 1 & (t ^ rx);
+            if ry == 0 {
+                if rx == 1 {
+                    x = s - 1 - x;
+                    y = s - 1 - y;
+                }
+                core::mem::swap(&mut x, &mut y);
+            }
+            x += s*rx;
+            y += s*ry;
+            t /= 4;
+            s *= 2;
+        }
+        (x, y)
+    }
+
+    #[test]
+    fn known_values() {
+        let level = 1;
+        assert_eq!(
+            (0..4).map(|d| d2xy(d, level)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (1, 1), (1, 0)],
+        );
+        assert_eq!(xy2d(1, 0, level), 3);
+        assert_eq!(xy2d(3, 0, 2), 15);
+        assert_eq!(d2xy(10, 2), (3, 3));
+    }
+
+    #[test]
+    fn same_as_wikipedia() {
+        for level in 0..7 {
+            let n = 1 << level;
+            for d in 0..n*n {
+                let (x, y) = d2xy(d, level);
+                assert_eq!((x, y), wikipedia_d2xy(n, d));
+                assert_eq!(xy2d(x, y, level), d);
+            }
+        }
+    }
+}
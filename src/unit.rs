@@ -0,0 +1,181 @@
+use crate::{FromHilbertIndex, ToHilbertIndex};
+
+/// Convert real coordinates in the unit cube `[0, 1)^D` to a Hilbert index.
+/// 
+/// Each coordinate is scaled by `2.pow(level)` and rounded down to a grid point.
+/// Coordinates outside the cube are clamped to the nearest cell: `1.0` (or above) goes to the last cell
+/// `2.pow(level) - 1` and negative values go to `0`.
+/// `NaN` is treated as `0.0`.
+/// In particular a coordinate which lands exactly on the upper edge after normalization goes to the last cell,
+/// as `to_hilbert_index_saturating` does for grid points.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_unit_coords, ToHilbertIndex};
+/// 
+/// let level = 2;
+/// assert_eq!( [1, 3].to_hilbert_index(level), from_unit_coords([0.3, 0.99], level) );
+/// assert_eq!( [3, 0].to_hilbert_index(level), from_unit_coords([1.0, 0.0], level) );
+/// ```
+/// 
+pub fn from_unit_coords<const D: usize>(coords: [f64; D], level: usize) -> usize {
+    let mut p = [0; D];
+    for (x, c) in p.iter_mut().zip(coords.iter()) {
+        *x = quantize(*c, level);
+    }
+    p.to_hilbert_index(level)
+}
+
+/// Convert a Hilbert index to the center of its cell in the unit cube.
+/// 
+/// The `k`-th coordinate is `(x[k] + 0.5) / 2.pow(level)`, where `x` is the grid point.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::to_unit_coords;
+/// 
+/// assert_eq!( [0.25, 0.75], to_unit_coords::<2>(1, 1) );
+/// ```
+/// 
+pub fn to_unit_coords<const D: usize>(h: usize, level: usize) -> [f64; D] {
+    let p: [usize; D] = h.from_hilbert_index(level);
+    let scale = (1u64 << level) as f64;
+    let mut coords = [0.0; D];
+    for (c, x) in coords.iter_mut().zip(p.iter()) {
+        *c = (*x as f64 + 0.5) / scale;
+    }
+    coords
+}
+
+/// Snap real coordinates in the unit cube `[0, 1]^D` to the Hilbert index of the cell whose center is the closest.
+/// 
+/// The center of the cell of a grid point `x` is at `(x[k] + 0.5) / 2.pow(level)` as in `to_unit_coords`.
+/// Inside a cell this is the same as `from_unit_coords`, but a point on the boundary between cells
+/// is equally close to the centers of several cells,
+/// and the tie is broken toward the cell of the smallest Hilbert index, i.e. the cell visited first by the curve,
+/// whereas `from_unit_coords` always rounds down to the cell on the upper side.
+/// Coordinates outside the cube are clamped and `NaN` is treated as `0.0`, as in `from_unit_coords`.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::{from_unit_coords, nearest_index_unit, ToHilbertIndex};
+/// 
+/// let level = 1;
+/// // on the boundary x = 0.5 between the cells [0, 1] (index 1) and [1, 1] (index 2)
+/// assert_eq!( [0, 1].to_hilbert_index(level), nearest_index_unit([0.5, 0.75], level) );
+/// assert_eq!( [1, 1].to_hilbert_index(level), from_unit_coords([0.5, 0.75], level) );
+/// // the center of the cube touches all four cells
+/// assert_eq!( 0, nearest_index_unit([0.5, 0.5], level) );
+/// ```
+/// 
+pub fn nearest_index_unit<const D: usize>(coords: [f64; D], level: usize) -> usize {
+    let mut p = [0; D];
+    // 境界上にあり, 下側の格子とも等距離にある軸
+    let mut tied = 0usize;
+    for (k, (x, c)) in p.iter_mut().zip(coords.iter()).enumerate() {
+        *x = quantize(*c, level);
+        if *x > 0 && *c * (1u64 << level) as f64 == *x as f64 {
+            tied |= 1 << k;
+        }
+    }
+
+    // 等距離の格子のうち添字が最小のものを選ぶ. tied の部分集合を全て調べる.
+    let mut best = p.to_hilbert_index(level);
+    let mut sub = tied;
+    while sub != 0 {
+        let mut q = p;
+        for (k, x) in q.iter_mut().enumerate() {
+            *x -= (sub >> k)&1;
+        }
+        best = best.min(q.to_hilbert_index(level));
+        sub = (sub - 1) & tied;
+    }
+    best
+}
+
+// [0, 1) の座標を 0..2^level の格子点に丸める.
+// `as usize` は負の値と NaN を 0 に, 大きすぎる値を usize::MAX に飽和させる.
+#[inline]
+fn quantize(c: f64, level: usize) -> usize {
+    let x = (c * (1u64 << level) as f64) as usize;
+    x.min((1 << level) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{from_unit_coords, indices, nearest_index_unit, to_unit_coords, ToHilbertIndex};
+
+    #[test]
+    fn round_trip() {
+        let level = 3;
+        for h in indices::<3>(level) {
+            assert_eq!(h, from_unit_coords(to_unit_coords::<3>(h, level), level));
+        }
+    }
+
+    #[test]
+    fn clamp_boundary() {
+        let level = 4;
+        assert_eq!(from_unit_coords([1.0, 1.0], level), [15, 15].to_hilbert_index(level));
+        assert_eq!(from_unit_coords([-0.5, 2.0], level), [0, 15].to_hilbert_index(level));
+        assert_eq!(from_unit_coords([f64::INFINITY, f64::NEG_INFINITY], level), [15, 0].to_hilbert_index(level));
+    }
+
+    #[test]
+    fn upper_edge() {
+        // 正規化 (x - min)/(max - min) で最大値はちょうど 1.0 になる
+        let (lo, hi) = (-3.7, 12.9);
+        let level = 5;
+        let c = (hi - lo)/(hi - lo);
+        assert_eq!(from_unit_coords([c, 0.0], level), [31, 0].to_hilbert_index(level));
+        assert_ne!(from_unit_coords([c, 0.0], level), 0);
+        assert_eq!(from_unit_coords([1.0 - f64::EPSILON/2.0, 0.0], level), [31, 0].to_hilbert_index(level));
+    }
+
+    #[test]
+    fn nearest_inside_cell() {
+        let level = 3;
+        for h in indices::<3>(level) {
+            let c = to_unit_coords::<3>(h, level);
+            assert_eq!(nearest_index_unit(c, level), h);
+            let c = [c[0] + 0.06, c[1] - 0.06, c[2] + 0.01];
+            assert_eq!(nearest_index_unit(c, level), from_unit_coords(c, level));
+        }
+    }
+
+    #[test]
+    fn nearest_tie_break() {
+        let level = 2;
+        let index = |p: [usize; 2]| p.to_hilbert_index(level);
+        // 辺上: 2 つの格子のうち添字の小さい方
+        assert_eq!(nearest_index_unit([0.25, 0.1], level), index([0, 0]).min(index([1, 0])));
+        assert_eq!(nearest_index_unit([0.6, 0.75], level), index([2, 2]).min(index([2, 3])));
+        assert_eq!(nearest_index_unit([0.5, 0.6], level), index([1, 2]).min(index([2, 2])));
+        // 頂点上: 4 つの格子のうち添字の最小のもの
+        let corner = [[1, 1], [1, 2], [2, 1], [2, 2]].iter().map(|&p| index(p)).min().unwrap();
+        assert_eq!(nearest_index_unit([0.5, 0.5], level), corner);
+        let corner = [[2, 0], [2, 1], [3, 0], [3, 1]].iter().map(|&p| index(p)).min().unwrap();
+        assert_eq!(nearest_index_unit([0.75, 0.25], level), corner);
+    }
+
+    #[test]
+    fn nearest_boundary() {
+        let level = 2;
+        // 立方体の表面は等距離の格子を持たない
+        assert_eq!(nearest_index_unit([0.0, 0.0], level), 0);
+        assert_eq!(nearest_index_unit([1.0, 0.0], level), [3, 0].to_hilbert_index(level));
+        assert_eq!(nearest_index_unit([1.0, 1.0], level), [3, 3].to_hilbert_index(level));
+        assert_eq!(nearest_index_unit([1.5, -2.0], level), [3, 0].to_hilbert_index(level));
+        assert_eq!(nearest_index_unit([f64::NAN, 0.3], level), [0, 1].to_hilbert_index(level));
+        assert_eq!(nearest_index_unit([0.5, 0.5], 0), 0);
+    }
+
+    #[test]
+    fn nan_is_zero() {
+        let level = 4;
+        assert_eq!(from_unit_coords([f64::NAN, 0.5], level), [0, 8].to_hilbert_index(level));
+    }
+}
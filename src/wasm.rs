@@ -0,0 +1,107 @@
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use wasm_bindgen::prelude::wasm_bindgen;
+use crate::DynHilbert;
+
+/// A Hilbert curve for JavaScript (requires the `wasm` feature).
+/// 
+/// The dimension is given at runtime as in `DynHilbert`, to which the conversions are delegated,
+/// and the coordinates and indices are passed as `u32` and `u64`, which cross the JS boundary
+/// as `Uint32Array` and `BigInt`. On `wasm32` the index is computed in `usize` of 32 bits,
+/// so `d*level` must be less than `32` there.
+/// 
+/// # Usage
+/// 
+/// ```
+/// use hilbert_index::HilbertJs;
+/// 
+/// let curve = HilbertJs::new(2, 3);
+/// assert_eq!( 2, curve.encode(&[1, 1]) );
+/// assert_eq!( vec![1, 1], curve.decode(2) );
+/// ```
+/// 
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HilbertJs {
+    d: usize,
+    level: usize,
+}
+
+#[wasm_bindgen]
+impl HilbertJs {
+    /// Create a `d`-dimensional Hilbert curve with a given level.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `d == 0` or `d*level >= usize::BITS`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(d: usize, level: usize) -> HilbertJs {
+        let curve = DynHilbert::new(d, level);
+        assert!(matches!(d.checked_mul(level), Some(n) if n < usize::BITS as usize),
+            "HilbertJs: d*level = {}*{} does not fit in usize of {} bits", d, level, usize::BITS);
+        HilbertJs { d: curve.d(), level: curve.level() }
+    }
+
+    /// The dimension of grid points.
+    #[wasm_bindgen(getter)]
+    pub fn d(&self) -> usize { self.d }
+
+    /// The level of the curve.
+    #[wasm_bindgen(getter)]
+    pub fn level(&self) -> usize { self.level }
+
+    /// Convert a grid point to a Hilbert index.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `coords.len() != d`.
+    pub fn encode(&self, coords: &[u32]) -> u64 {
+        let p: Vec<usize> = coords.iter().map(|&x| x as usize).collect();
+        self.curve().to_index(&p) as u64
+    }
+
+    /// Convert a Hilbert index to a grid point of length `d`.
+    /// 
+    /// # Panics
+    /// 
+    /// Panics if `h` does not fit in `usize`.
+    pub fn decode(&self, h: u64) -> Vec<u32> {
+        let h = usize::try_from(h)
+            .unwrap_or_else(|_| panic!("HilbertJs: index {} does not fit in usize", h));
+        // 各成分は level < usize::BITS bit だが, u32 に収まるのは level <= 32 のとき
+        self.curve().from_index(h).into_iter()
+            .map(|x| u32::try_from(x).expect("HilbertJs: component does not fit in u32"))
+            .collect()
+    }
+}
+
+impl HilbertJs {
+    #[inline]
+    fn curve(&self) -> DynHilbert {
+        DynHilbert::new(self.d, self.level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FromHilbertIndex, HilbertJs, ToHilbertIndex};
+
+    #[test]
+    fn same_as_const_generic() {
+        let level = 3;
+        let curve = HilbertJs::new(3, level);
+        assert_eq!((curve.d(), curve.level()), (3, level));
+        for h in crate::indices::<3>(level) {
+            let p: [usize; 3] = h.from_hilbert_index(level);
+            let coords: Vec<u32> = p.iter().map(|&x| x as u32).collect();
+            assert_eq!(curve.encode(&coords), p.to_hilbert_index(level) as u64);
+            assert_eq!(curve.decode(h as u64), coords);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in usize")]
+    fn too_large() {
+        HilbertJs::new(4, usize::BITS as usize / 4);
+    }
+}